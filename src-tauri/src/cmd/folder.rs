@@ -0,0 +1,89 @@
+use super::CmdResult;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::cmd::StringifyErr;
+use crate::core::folder::Folder;
+use crate::core::tab::TabManager;
+
+#[tauri::command]
+pub async fn create_folder_cmd(
+    window_label: String,
+    name: String,
+    parent_id: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Folder> {
+    let parent_uuid = parent_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .stringify_err()?;
+    let folder = tm
+        .lock()
+        .unwrap()
+        .create_folder(&window_label, &name, parent_uuid)?;
+    Ok(folder)
+}
+
+#[tauri::command]
+pub async fn rename_folder_cmd(
+    window_label: String,
+    folder_id: String,
+    name: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let folder_uuid = Uuid::parse_str(&folder_id).stringify_err()?;
+    tm.lock()
+        .unwrap()
+        .rename_folder(&window_label, folder_uuid, &name)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_folder_collapsed_cmd(
+    window_label: String,
+    folder_id: String,
+    collapsed: bool,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let folder_uuid = Uuid::parse_str(&folder_id).stringify_err()?;
+    tm.lock()
+        .unwrap()
+        .set_folder_collapsed(&window_label, folder_uuid, collapsed)?;
+    Ok(())
+}
+
+/// close_tabs 为 true 时一并关闭文件夹（含子文件夹）内的所有 tab
+#[tauri::command]
+pub async fn delete_folder_cmd(
+    app: AppHandle,
+    window_label: String,
+    folder_id: String,
+    close_tabs: bool,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let folder_uuid = Uuid::parse_str(&folder_id).stringify_err()?;
+    tm.lock()
+        .unwrap()
+        .delete_folder(&app, &window_label, folder_uuid, close_tabs)?;
+    Ok(())
+}
+
+/// folder_id 为空时移出到顶层
+#[tauri::command]
+pub async fn move_tab_to_folder_cmd(
+    window_label: String,
+    tab_id: String,
+    folder_id: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let folder_uuid = folder_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .stringify_err()?;
+    tm.lock()
+        .unwrap()
+        .move_tab_to_folder(&window_label, tab_uuid, folder_uuid)?;
+    Ok(())
+}
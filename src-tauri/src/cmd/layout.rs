@@ -4,6 +4,7 @@ use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, State};
 
 use crate::core::layout::{get_sidebar_width, sidebar_manager};
+use crate::core::session;
 use crate::core::tab::TabManager;
 
 /// 对外暴露获取侧栏宽度
@@ -20,6 +21,7 @@ pub async fn set_sidebar_width_cmd(
     tm: State<'_, Arc<Mutex<TabManager>>>,
 ) -> CmdResult<()> {
     sidebar_manager().update_width(&window_label, width);
+    session::mark_dirty();
 
     let tm = tm.lock().unwrap();
     tm.tab_resized(&app, &window_label);
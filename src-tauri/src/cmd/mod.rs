@@ -1,9 +1,17 @@
+pub mod folder;
 pub mod layout;
+pub mod settings;
+pub mod space;
+pub mod split;
 pub mod tab;
 
 pub type CmdResult<T = ()> = Result<T, String>;
 
+pub use folder::*;
 pub use layout::*;
+pub use settings::*;
+pub use space::*;
+pub use split::*;
 pub use tab::*;
 
 pub trait StringifyErr<T> {
@@ -0,0 +1,41 @@
+use super::CmdResult;
+use tauri::AppHandle;
+
+use crate::cmd::StringifyErr;
+use crate::core::popup::{self, NewWindowAction, NewWindowRule};
+use crate::core::settings::{self, CloseTabActivation, Settings};
+
+#[tauri::command]
+pub fn get_settings_cmd() -> CmdResult<Settings> {
+    Ok(settings::settings())
+}
+
+/// 设置关闭活跃 tab 后激活最近使用的 tab 还是相邻的 tab
+#[tauri::command]
+pub fn set_close_tab_activation_cmd(
+    app: AppHandle,
+    activation: CloseTabActivation,
+) -> CmdResult<Settings> {
+    settings::update_settings(&app, |s| s.close_tab_activation = activation).stringify_err()
+}
+
+/// 设置页面打开新窗口时用 tab 还是浮动窗口，rules 按域名覆盖默认处理方式
+#[tauri::command]
+pub fn set_new_window_policy_cmd(
+    app: AppHandle,
+    action: NewWindowAction,
+    rules: Vec<NewWindowRule>,
+) -> CmdResult<Settings> {
+    settings::update_settings(&app, |s| {
+        s.new_window_action = action;
+        s.new_window_rules = rules;
+    })
+    .stringify_err()
+}
+
+/// 移除站点的弹窗例外
+#[tauri::command]
+pub fn remove_popup_exception_cmd(app: AppHandle, origin: String) -> CmdResult<Settings> {
+    popup::remove_origin(&app, &origin).stringify_err()?;
+    Ok(settings::settings())
+}
@@ -0,0 +1,107 @@
+use super::CmdResult;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::cmd::StringifyErr;
+use crate::core::session;
+use crate::core::space::Space;
+use crate::core::tab::TabManager;
+
+#[tauri::command]
+pub fn get_space_list_cmd(tm: State<'_, Arc<Mutex<TabManager>>>) -> CmdResult<Vec<Space>> {
+    Ok(tm.lock().unwrap().spaces().list())
+}
+
+/// 窗口当前显示的 space
+#[tauri::command]
+pub fn get_window_space_cmd(
+    window_label: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<String> {
+    let space_id = tm.lock().unwrap().get_window_space_id(&window_label);
+    Ok(space_id.to_string())
+}
+
+#[tauri::command]
+pub async fn create_space_cmd(
+    name: String,
+    icon: Option<String>,
+    color: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Space> {
+    let space = tm.lock().unwrap().spaces_mut().create(&name, icon, color);
+    session::mark_dirty();
+    Ok(space)
+}
+
+#[tauri::command]
+pub async fn rename_space_cmd(
+    space_id: String,
+    name: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let space_uuid = Uuid::parse_str(&space_id).stringify_err()?;
+    tm.lock().unwrap().spaces_mut().rename(&space_uuid, &name)?;
+    session::mark_dirty();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_space_appearance_cmd(
+    space_id: String,
+    icon: Option<String>,
+    color: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let space_uuid = Uuid::parse_str(&space_id).stringify_err()?;
+    tm.lock()
+        .unwrap()
+        .spaces_mut()
+        .set_appearance(&space_uuid, icon, color)?;
+    session::mark_dirty();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn reorder_space_cmd(
+    space_id: String,
+    new_index: usize,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Vec<Space>> {
+    let space_uuid = Uuid::parse_str(&space_id).stringify_err()?;
+    let spaces = tm
+        .lock()
+        .unwrap()
+        .spaces_mut()
+        .reorder(&space_uuid, new_index)?;
+    session::mark_dirty();
+    Ok(spaces)
+}
+
+#[tauri::command]
+pub async fn delete_space_cmd(
+    app: AppHandle,
+    space_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let space_uuid = Uuid::parse_str(&space_id).stringify_err()?;
+    tm.lock().unwrap().delete_space(&app, space_uuid)?;
+    Ok(())
+}
+
+/// 切换 space，返回新 space 的活跃 tab
+#[tauri::command]
+pub async fn switch_space_cmd(
+    app: AppHandle,
+    window_label: String,
+    space_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Option<String>> {
+    let space_uuid = Uuid::parse_str(&space_id).stringify_err()?;
+    let active_tab_id = tm
+        .lock()
+        .unwrap()
+        .switch_space(&app, &window_label, space_uuid)?;
+    Ok(active_tab_id.map(|id| id.to_string()))
+}
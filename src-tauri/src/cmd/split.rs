@@ -0,0 +1,94 @@
+use super::CmdResult;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::cmd::StringifyErr;
+use crate::core::split::{SplitLayout, SplitViewInfo};
+use crate::core::tab::TabManager;
+
+#[tauri::command]
+pub async fn get_split_view_cmd(
+    window_label: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let split = tm.lock().unwrap().get_split_view(&window_label)?;
+    Ok(split)
+}
+
+/// 把 tab 加入当前窗口的分屏
+#[tauri::command]
+pub async fn add_tab_to_split_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let split = tm
+        .lock()
+        .unwrap()
+        .add_tab_to_split(&app, &window_label, tab_uuid)?;
+    Ok(split)
+}
+
+#[tauri::command]
+pub async fn remove_tab_from_split_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let split = tm
+        .lock()
+        .unwrap()
+        .remove_tab_from_split(&app, &window_label, tab_uuid)?;
+    Ok(split)
+}
+
+#[tauri::command]
+pub async fn swap_split_panes_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    other_tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let other_tab_uuid = Uuid::parse_str(&other_tab_id).stringify_err()?;
+    let split =
+        tm.lock()
+            .unwrap()
+            .swap_split_panes(&app, &window_label, tab_uuid, other_tab_uuid)?;
+    Ok(split)
+}
+
+#[tauri::command]
+pub async fn set_split_layout_cmd(
+    app: AppHandle,
+    window_label: String,
+    layout: SplitLayout,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let split = tm
+        .lock()
+        .unwrap()
+        .set_split_layout(&app, &window_label, layout)?;
+    Ok(split)
+}
+
+/// 调整分隔条位置，取值为内容区的 0..1 占比
+#[tauri::command]
+pub async fn set_split_ratios_cmd(
+    app: AppHandle,
+    window_label: String,
+    ratios: Vec<f64>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<SplitViewInfo> {
+    let split = tm
+        .lock()
+        .unwrap()
+        .set_split_ratios(&app, &window_label, ratios)?;
+    Ok(split)
+}
@@ -4,30 +4,41 @@ use tauri::{AppHandle, State};
 use uuid::Uuid;
 
 use crate::cmd::StringifyErr;
-use crate::core::tab::{Tab, TabManager, TabNavigation};
+use crate::core::closed_tab::ClosedTab;
+use crate::core::find::{self, FindOptions};
+use crate::core::hibernation;
+use crate::core::history::TabHistoryInfo;
+use crate::core::popup::{self, BlockedPopup};
+use crate::core::setup::{
+    create_browser_window, new_window_label, open_new_window, DEFAULT_SIDEBAR_WIDTH,
+};
+use crate::core::tab::{CloseTabsRule, Tab, TabInfoList, TabManager, TabNavigation};
+use crate::core::zoom::ZoomAction;
 
 #[tauri::command]
 pub fn get_tab_info_list_cmd(
     window_label: String,
     tab_manager: State<'_, Arc<Mutex<TabManager>>>,
-) -> CmdResult<Vec<Tab>> {
+) -> CmdResult<TabInfoList> {
     let tabs = (*tab_manager)
         .lock()
         .unwrap()
-        .get_tab_info_list(window_label);
+        .get_tab_info_list(&window_label);
     Ok(tabs)
 }
 
+/// 创建 tab，deferred 为 true 时在后台打开，首次切换时才加载页面
 #[tauri::command]
 pub async fn create_tab_cmd(
     app: AppHandle,
     window_label: String,
     url: String,
     name: String,
+    deferred: Option<bool>,
     tm: State<'_, Arc<Mutex<TabManager>>>,
 ) -> CmdResult<String> {
     let mut tm = tm.lock().unwrap();
-    let tab_id = tm.create_tab(&app, &window_label, &url, &name)?;
+    let tab_id = tm.create_tab(&app, &window_label, &url, &name, deferred.unwrap_or(false))?;
     Ok(tab_id.to_string())
 }
 
@@ -52,15 +63,201 @@ pub async fn close_tab_cmd(
     window_label: String,
     tab_id: String,
     tm: State<'_, Arc<Mutex<TabManager>>>,
-) -> CmdResult {
+) -> CmdResult<Option<String>> {
+    let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
+
+    let mut tm = tm.lock().unwrap();
+    let active_tab_id = tm.close_tab(&app, &window_label, tab_uuid)?;
+
+    Ok(active_tab_id.map(|id| id.to_string()))
+}
+
+/// 复制 tab 到其后方并激活，返回新 tab id
+#[tauri::command]
+pub async fn duplicate_tab_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<String> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+
+    let mut tm = tm.lock().unwrap();
+    let new_tab_id = tm.duplicate_tab(&app, &window_label, tab_uuid)?;
+    Ok(new_tab_id.to_string())
+}
+
+/// 按规则批量关闭 tab，可作为一组撤销，返回被关闭的 tab id
+#[tauri::command]
+pub async fn close_tabs_cmd(
+    app: AppHandle,
+    window_label: String,
+    rule: CloseTabsRule,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Vec<String>> {
+    let mut tm = tm.lock().unwrap();
+    let closed = tm.close_tabs_by_rule(&app, &window_label, rule)?;
+    Ok(closed.iter().map(|id| id.to_string()).collect())
+}
+
+/// Ctrl+Tab 切换器：当前 space 的 tab，按最近使用排序
+#[tauri::command]
+pub fn get_mru_tab_list_cmd(
+    window_label: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Vec<Tab>> {
+    Ok(tm.lock().unwrap().get_mru_tabs(&window_label))
+}
+
+/// 移动 tab 到指定位置，folder_id 为空时移动到顶层，返回新的排序
+#[tauri::command]
+pub async fn move_tab_cmd(
+    window_label: String,
+    tab_id: String,
+    new_index: usize,
+    folder_id: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<TabInfoList> {
+    let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
+    let folder_uuid = folder_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .stringify_err()?;
+
+    let mut tm = tm.lock().unwrap();
+    let tabs = tm.move_tab(&window_label, tab_uuid, new_index, folder_uuid)?;
+    Ok(tabs)
+}
+
+/// 新建浏览器窗口并打开一个 tab，url 为空时打开默认页面，private 为 true 时为隐私窗口，返回窗口 label
+#[tauri::command]
+pub async fn new_window_cmd(
+    app: AppHandle,
+    url: Option<String>,
+    private: Option<bool>,
+) -> CmdResult<String> {
+    open_new_window(&app, url.as_deref(), private.unwrap_or(false)).stringify_err()
+}
+
+/// 窗口是否为隐私窗口
+#[tauri::command]
+pub fn is_private_window_cmd(
+    window_label: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<bool> {
+    Ok(tm.lock().unwrap().is_private_window(&window_label))
+}
+
+/// 把 tab 移动到另一个窗口，target_window_label 为空时新建窗口，返回目标窗口 label
+#[tauri::command]
+pub async fn move_tab_to_window_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    target_window_label: Option<String>,
+    index: Option<usize>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<String> {
     let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
 
+    // 新窗口需在加锁前创建，窗口事件回调同样会获取 TabManager
+    let is_new_window = target_window_label.is_none();
+    let target_window_label = match target_window_label {
+        Some(label) => label,
+        None => {
+            let label = new_window_label();
+            create_browser_window(&app, &label, None, DEFAULT_SIDEBAR_WIDTH).stringify_err()?;
+            label
+        }
+    };
+
     let mut tm = tm.lock().unwrap();
-    tm.close_tab(&app, &window_label, tab_uuid)?;
+    // 隐私窗口的 tab 拖出时新窗口同样是隐私窗口，并沿用同一数据存储
+    if let Some(store_id) = tm.private_store(&window_label).filter(|_| is_new_window) {
+        tm.set_window_private(&target_window_label, store_id);
+    }
+    tm.move_tab_to_window(&app, &window_label, tab_uuid, &target_window_label, index)?;
+
+    Ok(target_window_label)
+}
+
+#[tauri::command]
+pub async fn pin_tab_cmd(
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
+    tm.lock().unwrap().pin_tab(&window_label, tab_uuid)?;
+    Ok(())
+}
 
+#[tauri::command]
+pub async fn unpin_tab_cmd(
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
+    tm.lock().unwrap().unpin_tab(&window_label, tab_uuid)?;
+    Ok(())
+}
+
+/// 固定 tab 回到其主页地址
+#[tauri::command]
+pub async fn reset_pinned_tab_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).map_err(|e| e.to_string())?;
+    tm.lock()
+        .unwrap()
+        .reset_pinned_tab(&app, &window_label, tab_uuid)
+        .stringify_err()?;
     Ok(())
 }
 
+/// 最近关闭的 tab 列表，供历史菜单使用
+#[tauri::command]
+pub fn list_closed_tabs_cmd(
+    window_label: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Vec<ClosedTab>> {
+    Ok(tm.lock().unwrap().list_closed_tabs(&window_label))
+}
+
+/// 重新打开最近一次关闭的 tab（批量关闭时为整组），传入 tab_id 时打开指定的一个，返回激活的 tab id
+#[tauri::command]
+pub async fn reopen_closed_tab_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: Option<String>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<String> {
+    let tab_uuid = tab_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .stringify_err()?;
+
+    let mut tm = tm.lock().unwrap();
+    let new_tab_id = tm.reopen_closed_tab(&app, &window_label, tab_uuid)?;
+    Ok(new_tab_id.to_string())
+}
+
+/// 获取后台 tab 的休眠闲置时长（秒），0 表示关闭休眠
+#[tauri::command]
+pub fn get_hibernate_after_cmd() -> CmdResult<u64> {
+    Ok(hibernation::hibernate_after().map_or(0, |d| d.as_secs()))
+}
+
+/// 设置后台 tab 的休眠闲置时长（秒），0 关闭休眠
+#[tauri::command]
+pub fn set_hibernate_after_cmd(app: AppHandle, seconds: u64) -> CmdResult<()> {
+    hibernation::set_hibernate_after(&app, seconds).stringify_err()
+}
+
 #[tauri::command]
 pub async fn navigate_tab_cmd(
     app: AppHandle,
@@ -106,3 +303,162 @@ pub async fn tab_history_cmd(
 
     Ok(())
 }
+
+/// tab 的导航历史，包含能否前进/后退，供后退长按菜单使用
+#[tauri::command]
+pub fn get_tab_history_cmd(
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<TabHistoryInfo> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let history = tm
+        .lock()
+        .unwrap()
+        .get_tab_history(&window_label, tab_uuid)?;
+    Ok(history)
+}
+
+/// 跳转到导航历史中的指定记录
+#[tauri::command]
+pub async fn go_to_history_entry_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    index: usize,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let mut tm = tm.lock().unwrap();
+    tm.navigate(
+        &app,
+        &window_label,
+        tab_uuid,
+        TabNavigation::GoToEntry(index),
+    )
+    .stringify_err()?;
+
+    Ok(())
+}
+
+/// 放大 tab，同站点的 tab 一起生效，返回新的缩放比例
+#[tauri::command]
+pub async fn zoom_in_tab_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<f64> {
+    zoom_tab(&app, &window_label, &tab_id, ZoomAction::In, &tm)
+}
+
+/// 缩小 tab，同站点的 tab 一起生效，返回新的缩放比例
+#[tauri::command]
+pub async fn zoom_out_tab_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<f64> {
+    zoom_tab(&app, &window_label, &tab_id, ZoomAction::Out, &tm)
+}
+
+/// 恢复 tab 默认缩放，并清除站点记住的比例
+#[tauri::command]
+pub async fn reset_tab_zoom_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<f64> {
+    zoom_tab(&app, &window_label, &tab_id, ZoomAction::Reset, &tm)
+}
+
+fn zoom_tab(
+    app: &AppHandle,
+    window_label: &str,
+    tab_id: &str,
+    action: ZoomAction,
+    tm: &State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<f64> {
+    let tab_uuid = Uuid::parse_str(tab_id).stringify_err()?;
+    let mut tm = tm.lock().unwrap();
+    let zoom = tm.zoom_tab(app, window_label, tab_uuid, action)?;
+    Ok(zoom)
+}
+
+/// 页内查找并高亮所有匹配，结果通过 tab 事件 findResult 返回
+#[tauri::command]
+pub fn find_in_page_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    query: String,
+    options: Option<FindOptions>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    find::find_in_page(
+        &app,
+        &window_label,
+        &tab_uuid,
+        &query,
+        options.unwrap_or_default(),
+    )
+}
+
+/// 跳到下一个匹配，forward 为 false 时跳到上一个
+#[tauri::command]
+pub fn find_next_in_page_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    forward: Option<bool>,
+) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    find::find_step(&app, &window_label, &tab_uuid, forward.unwrap_or(true))
+}
+
+/// 结束页内查找并清除高亮
+#[tauri::command]
+pub fn stop_find_in_page_cmd(app: AppHandle, window_label: String, tab_id: String) -> CmdResult {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    find::stop_find(&app, &window_label, &tab_uuid)
+}
+
+/// tab 当前页面被拦截的弹窗
+#[tauri::command]
+pub fn get_blocked_popups_cmd(
+    window_label: String,
+    tab_id: String,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<Vec<BlockedPopup>> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    Ok(tm
+        .lock()
+        .unwrap()
+        .get_blocked_popups(&window_label, &tab_uuid))
+}
+
+/// 打开被拦截的弹窗，always 为 true 时始终允许拦截时记录的 origin 弹窗，返回新 tab id
+#[tauri::command]
+pub async fn allow_blocked_popup_cmd(
+    app: AppHandle,
+    window_label: String,
+    tab_id: String,
+    url: String,
+    always: Option<bool>,
+    tm: State<'_, Arc<Mutex<TabManager>>>,
+) -> CmdResult<String> {
+    let tab_uuid = Uuid::parse_str(&tab_id).stringify_err()?;
+    let blocked = tm
+        .lock()
+        .unwrap()
+        .take_blocked_popup(&window_label, &tab_uuid, &url)?;
+    if always.unwrap_or(false) {
+        popup::allow_origin(&app, &blocked.origin).stringify_err()?;
+    }
+
+    let mut tm = tm.lock().unwrap();
+    let new_tab_id = tm.open_tab_from_opener(&app, tab_uuid, &blocked.url)?;
+    Ok(new_tab_id.to_string())
+}
@@ -0,0 +1,165 @@
+use serde::Deserialize;
+use tauri::Url;
+use uuid::Uuid;
+
+// 页面向 Rust 发送消息使用的伪协议，导航会在 on_navigation 中被拦截并取消
+pub const BRIDGE_SCHEME: &str = "rin-bridge";
+
+// 桥接脚本中的令牌占位符
+const TOKEN_PLACEHOLDER: &str = "__RIN_BRIDGE_TOKEN__";
+
+// 注入到每个 tab 主 frame 的桥接脚本：通过隐藏 iframe 导航到 rin-bridge:// 传递消息，
+// 消息附带只有主 frame 脚本知道的令牌，子 frame 伪造的消息会被忽略
+const BRIDGE_SCRIPT: &str = r#"
+(function () {
+  if (window.top !== window || window.__rinBridge) return;
+
+  const token = "__RIN_BRIDGE_TOKEN__";
+  const send = (message) => {
+    const frame = document.createElement("iframe");
+    frame.style.display = "none";
+    frame.src = "rin-bridge://message?m=" + encodeURIComponent(JSON.stringify({ ...message, token }));
+    (document.body || document.documentElement).appendChild(frame);
+    setTimeout(() => frame.remove(), 1000);
+  };
+  Object.defineProperty(window, "__rinBridge", { value: send });
+
+  // 加载进度
+  document.addEventListener("readystatechange", () => {
+    if (document.readyState === "interactive") send({ kind: "progress", progress: 0.7 });
+  });
+
+  // 音频播放状态
+  let isPlaying = false;
+  const checkAudio = () => {
+    const playing = Array.from(document.querySelectorAll("audio, video")).some(
+      (m) => !m.paused && !m.muted && m.volume > 0
+    );
+    if (playing !== isPlaying) {
+      isPlaying = playing;
+      send({ kind: "audio", isPlaying });
+    }
+  };
+  for (const type of ["play", "pause", "ended", "volumechange", "emptied"]) {
+    document.addEventListener(type, checkAudio, true);
+  }
+
+  // 单页应用的地址变化
+  const notifyUrl = (replace) => send({ kind: "urlChanged", replace });
+  for (const name of ["pushState", "replaceState"]) {
+    const original = history[name];
+    history[name] = function (...args) {
+      const result = original.apply(this, args);
+      notifyUrl(name === "replaceState");
+      return result;
+    };
+  }
+  window.addEventListener("popstate", () => notifyUrl(false));
+  window.addEventListener("hashchange", () => notifyUrl(false));
+
+  // 页面图标：优先 <link rel=icon>，否则 /favicon.ico；跨域无法读取时只上报地址
+  const readAsDataUrl = (blob) =>
+    new Promise((resolve) => {
+      const reader = new FileReader();
+      reader.onload = () => resolve(reader.result);
+      reader.onerror = () => resolve(null);
+      reader.readAsDataURL(blob);
+    });
+  const reportFavicon = async () => {
+    if (!location.protocol.startsWith("http")) return;
+    const links = document.querySelectorAll('link[rel~="icon"]');
+    const href = links.length ? links[links.length - 1].href : location.origin + "/favicon.ico";
+    let data = null;
+    try {
+      const response = await fetch(href);
+      const blob = await response.blob();
+      if (response.ok && blob.size > 0 && blob.size <= 256 * 1024) {
+        data = await readAsDataUrl(blob);
+      }
+    } catch (_) {}
+    send({ kind: "favicon", href, data });
+  };
+  window.addEventListener("load", reportFavicon);
+})();
+"#;
+
+/// 带令牌的桥接消息
+#[derive(Deserialize)]
+struct BridgeEnvelope {
+    token: String,
+    #[serde(flatten)]
+    message: BridgeMessage,
+}
+
+/// 页面发来的消息，地址由 Rust 从 webview 读取，不信任页面上报
+#[derive(Debug, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum BridgeMessage {
+    Progress {
+        progress: f64,
+    },
+    Audio {
+        is_playing: bool,
+    },
+    UrlChanged {
+        replace: bool,
+    },
+    Favicon {
+        href: String,
+        data: Option<String>,
+    },
+    // 页内查找结果，active_match 从 1 开始，没有匹配时为 0
+    FindResult {
+        query: String,
+        active_match: u32,
+        match_count: u32,
+    },
+    // 复制 tab 时源页面上报的滚动位置，target 为新 tab
+    ScrollPosition {
+        target: Uuid,
+        x: f64,
+        y: f64,
+    },
+}
+
+/// 在源页面执行：上报滚动位置给复制出的 tab
+pub fn report_scroll_script(target: &Uuid) -> String {
+    format!(
+        r#"window.__rinBridge && window.__rinBridge({{ kind: "scrollPosition", target: "{}", x: window.scrollX, y: window.scrollY }});"#,
+        target
+    )
+}
+
+/// 在新页面执行：恢复滚动位置，页面未加载完时等待 load
+pub fn scroll_to_script(x: f64, y: f64) -> String {
+    format!(
+        r#"(function () {{
+  const restore = () => window.scrollTo({}, {});
+  if (document.readyState === "complete") restore();
+  else window.addEventListener("load", restore, {{ once: true }});
+}})();"#,
+        x, y
+    )
+}
+
+/// 生成 tab 的桥接脚本，token 为该 webview 独有的令牌
+pub fn bridge_script(token: &str) -> String {
+    BRIDGE_SCRIPT.replace(TOKEN_PLACEHOLDER, token)
+}
+
+/// 解析桥接消息，格式为 rin-bridge://message?m=<json>，令牌不符的消息视为来自子 frame 并忽略
+pub fn parse_bridge_message(url: &Url, token: &str) -> Option<BridgeMessage> {
+    let (_, data) = url.query_pairs().find(|(key, _)| key == "m")?;
+    match serde_json::from_str::<BridgeEnvelope>(&data) {
+        Ok(envelope) if envelope.token == token => Some(envelope.message),
+        Ok(_) => None,
+        Err(e) => {
+            eprintln!("[Bridge:parse] Ignoring malformed message: {}", e);
+            None
+        }
+    }
+}
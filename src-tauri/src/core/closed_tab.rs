@@ -0,0 +1,91 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+use crate::core::tab::Tab;
+
+// 每个窗口最多保留的已关闭 tab 数量
+const MAX_CLOSED_TABS: usize = 25;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosedTab {
+    // 关闭时的 tab 快照：url、标题、位置、文件夹与 space
+    pub tab: Tab,
+    // 关闭时间（毫秒时间戳）
+    pub closed_at: u64,
+    // 同一次操作关闭的 tab 属于同一组，撤销时一起恢复
+    pub group_id: Uuid,
+    // 关闭时是否为活跃 tab
+    pub was_active: bool,
+}
+
+// 单个窗口最近关闭的 tab，最新的在最前
+#[derive(Default)]
+pub struct ClosedTabs {
+    entries: VecDeque<ClosedTab>,
+}
+
+impl ClosedTabs {
+    /// 记录一次关闭操作，tabs 中的 tab 作为一组
+    pub fn push_group(&mut self, tabs: Vec<(Tab, bool)>, closed_at: u64) {
+        let group_id = Uuid::new_v4();
+        for (tab, was_active) in tabs.into_iter().rev() {
+            self.entries.push_front(ClosedTab {
+                tab,
+                closed_at,
+                group_id,
+                was_active,
+            });
+        }
+        self.entries.truncate(MAX_CLOSED_TABS);
+    }
+
+    pub fn list(&self) -> Vec<ClosedTab> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// 查看 take 将取出的条目，不从列表中移除
+    pub fn peek(&self, tab_id: Option<Uuid>) -> Vec<ClosedTab> {
+        match tab_id {
+            Some(id) => self
+                .entries
+                .iter()
+                .find(|e| e.tab.id == id)
+                .cloned()
+                .into_iter()
+                .collect(),
+            None => {
+                let Some(group_id) = self.entries.front().map(|e| e.group_id) else {
+                    return Vec::new();
+                };
+                self.entries
+                    .iter()
+                    .filter(|e| e.group_id == group_id)
+                    .cloned()
+                    .collect()
+            }
+        }
+    }
+
+    /// 取出指定 tab；未指定时取出最近一次关闭的整组
+    pub fn take(&mut self, tab_id: Option<Uuid>) -> Vec<ClosedTab> {
+        match tab_id {
+            Some(id) => self
+                .entries
+                .iter()
+                .position(|e| e.tab.id == id)
+                .and_then(|position| self.entries.remove(position))
+                .into_iter()
+                .collect(),
+            None => {
+                let Some(group_id) = self.entries.front().map(|e| e.group_id) else {
+                    return Vec::new();
+                };
+                let (group, rest) = self.entries.drain(..).partition(|e| e.group_id == group_id);
+                self.entries = rest;
+                group.into()
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use objc2::runtime::{AnyClass, AnyObject, Imp, Sel};
+use objc2::{ffi, msg_send, sel};
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use crate::core::tab_event::{emit_tab_event, TabEventKind};
+
+type TerminateFn = unsafe extern "C-unwind" fn(*mut AnyObject, Sel, *mut AnyObject);
+
+static APP: OnceCell<AppHandle> = OnceCell::new();
+
+// WKWebView 地址 -> tab
+static WATCHED: Lazy<Mutex<HashMap<usize, Uuid>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// 已接管 webViewWebContentProcessDidTerminate: 的导航代理类 -> 原实现，没有原实现时为 None
+static HOOKED: Lazy<Mutex<HashMap<usize, Option<Imp>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 监听 tab webview 的网页内容进程终止，崩溃时推送 Crashed 事件。
+///
+/// # Safety
+/// webview 必须是有效的 WKWebView，且在主线程调用
+pub unsafe fn watch(app: &AppHandle, tab_id: Uuid, webview: *mut c_void) {
+    APP.get_or_init(|| app.clone());
+    let webview = webview as *mut AnyObject;
+    WATCHED.lock().unwrap().insert(webview as usize, tab_id);
+
+    let delegate: *mut AnyObject = msg_send![&*webview, navigationDelegate];
+    if delegate.is_null() {
+        return;
+    }
+    // 导航代理由 wry 创建，同一个类只接管一次
+    let class = (*delegate).class();
+    let mut hooked = HOOKED.lock().unwrap();
+    let key = class as *const AnyClass as usize;
+    if hooked.contains_key(&key) {
+        return;
+    }
+    let selector = sel!(webViewWebContentProcessDidTerminate:);
+    let imp: Imp = std::mem::transmute(did_terminate as TerminateFn);
+    let original = match class.instance_method(selector) {
+        Some(method) => Some(method.set_implementation(imp)),
+        None => {
+            ffi::class_addMethod(
+                class as *const AnyClass as *mut AnyClass,
+                selector,
+                imp,
+                c"v@:@".as_ptr(),
+            );
+            None
+        }
+    };
+    hooked.insert(key, original);
+}
+
+unsafe extern "C-unwind" fn did_terminate(this: *mut AnyObject, sel: Sel, webview: *mut AnyObject) {
+    // 先执行 wry 原有的处理
+    let original = {
+        let hooked = HOOKED.lock().unwrap();
+        let mut class = Some((*this).class());
+        let mut original = None;
+        while let Some(current) = class {
+            if let Some(imp) = hooked.get(&(current as *const AnyClass as usize)) {
+                original = *imp;
+                break;
+            }
+            class = current.superclass();
+        }
+        original
+    };
+    if let Some(original) = original {
+        let original: TerminateFn = std::mem::transmute(original);
+        original(this, sel, webview);
+    }
+
+    let tab_id = WATCHED.lock().unwrap().get(&(webview as usize)).copied();
+    if let (Some(app), Some(tab_id)) = (APP.get(), tab_id) {
+        notify_crashed(app, tab_id);
+    }
+}
+
+fn notify_crashed(app: &AppHandle, tab_id: Uuid) {
+    // tab 已关闭时 webview 不存在，不再推送
+    let Some(webview) = app.get_webview(&tab_id.to_string()) else {
+        return;
+    };
+    eprintln!(
+        "[Tab:crash] Web content process of tab {} terminated",
+        tab_id
+    );
+    let window_label = webview.window().label().to_string();
+    emit_tab_event(app, &window_label, tab_id, TabEventKind::Crashed);
+}
@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::fs;
+use std::path::PathBuf;
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager};
+
+use crate::core::tab::now_millis;
+
+// UI 读取缓存图标使用的自定义协议
+pub const FAVICON_SCHEME: &str = "favicon";
+const FAVICON_DIR: &str = "favicons";
+// 单个图标大小上限
+const MAX_FAVICON_BYTES: usize = 256 * 1024;
+
+// 支持的图标格式：扩展名与 Content-Type
+const FAVICON_TYPES: [(&str, &str); 6] = [
+    ("png", "image/png"),
+    ("ico", "image/x-icon"),
+    ("svg", "image/svg+xml"),
+    ("jpg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+];
+
+/// 把页面读取到的图标（data URL）写入缓存，返回 UI 可用的地址
+pub fn store_data_url(app: &AppHandle, origin: &str, data_url: &str) -> Result<String> {
+    let (_, data) = data_url
+        .split_once(";base64,")
+        .context("Unsupported favicon data URL")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .context("Failed to decode favicon")?;
+    if bytes.is_empty() || bytes.len() > MAX_FAVICON_BYTES {
+        anyhow::bail!("Favicon size {} out of range", bytes.len());
+    }
+    // 服务器返回的 MIME 常常不准确，按文件头识别格式
+    let ext = sniff_extension(&bytes).context("Unsupported favicon format")?;
+
+    let dir = cache_dir(app)?;
+    fs::create_dir_all(&dir).context("Failed to create favicon cache dir")?;
+    let key = cache_key(origin);
+    // 同一 origin 只保留一个图标
+    for (old_ext, _) in FAVICON_TYPES.iter() {
+        let _ = fs::remove_file(dir.join(format!("{}.{}", key, old_ext)));
+    }
+    fs::write(dir.join(format!("{}.{}", key, ext)), &bytes)
+        .context("Failed to write favicon cache")?;
+
+    Ok(favicon_url(&key, now_millis()))
+}
+
+/// origin 已缓存的图标地址
+pub fn cached_favicon_url(app: &AppHandle, origin: &str) -> Option<String> {
+    let key = cache_key(origin);
+    let (path, _) = find_cached(app, &key)?;
+    let version = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64);
+    Some(favicon_url(&key, version))
+}
+
+/// 自定义协议处理：favicon://localhost/<key>
+pub fn serve(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let key = request.uri().path().trim_start_matches('/');
+    let cached = is_valid_key(key)
+        .then(|| find_cached(app, key))
+        .flatten()
+        .and_then(|(path, content_type)| Some((fs::read(path).ok()?, content_type)));
+
+    let response = match cached {
+        Some((data, content_type)) => Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::CACHE_CONTROL, "max-age=31536000")
+            .body(data),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Vec::new()),
+    };
+    response.unwrap_or_default()
+}
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf> {
+    Ok(app
+        .path()
+        .app_cache_dir()
+        .context("Failed to resolve app cache dir")?
+        .join(FAVICON_DIR))
+}
+
+/// 缓存文件名：origin 的小写十六进制编码，不同 origin 不会映射到同一文件，
+/// 也不受文件系统大小写不敏感影响
+fn cache_key(origin: &str) -> String {
+    origin.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() % 2 == 0 && key.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+}
+
+fn find_cached(app: &AppHandle, key: &str) -> Option<(PathBuf, &'static str)> {
+    let dir = cache_dir(app).ok()?;
+    FAVICON_TYPES.iter().find_map(|(ext, content_type)| {
+        let path = dir.join(format!("{}.{}", key, ext));
+        path.exists().then_some((path, *content_type))
+    })
+}
+
+// Windows 上自定义协议以 http://<scheme>.localhost 访问
+fn favicon_url(key: &str, version: u64) -> String {
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}?v={}", FAVICON_SCHEME, key, version)
+    } else {
+        format!("{}://localhost/{}?v={}", FAVICON_SCHEME, key, version)
+    }
+}
+
+fn sniff_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG") {
+        Some("png")
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        Some("ico")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(&b"WEBP"[..]) {
+        Some("webp")
+    } else if String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).contains("<svg") {
+        Some("svg")
+    } else {
+        None
+    }
+}
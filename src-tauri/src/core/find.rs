@@ -0,0 +1,194 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, Webview};
+use uuid::Uuid;
+
+// 注入到每个 tab 主 frame 的页内查找脚本，平台没有原生查找 API，
+// 匹配项用 <mark> 包裹高亮，结果通过桥接消息上报
+pub const FIND_SCRIPT: &str = r#"
+(function () {
+  if (window.top !== window || window.__rinFind) return;
+
+  const MARK_ATTR = "data-rin-find";
+  const SKIP_SELECTOR = "script, style, noscript, textarea, select, option";
+  const style = document.createElement("style");
+  style.textContent =
+    `mark[${MARK_ATTR}] { background: #ffe564; color: inherit; padding: 0; }` +
+    `mark[${MARK_ATTR}="active"] { background: #ff9632; }`;
+
+  let marks = [];
+  let active = -1;
+  let lastQuery = "";
+  let lastOptions = {};
+
+  const report = () =>
+    window.__rinBridge &&
+    window.__rinBridge({
+      kind: "findResult",
+      query: lastQuery,
+      activeMatch: active + 1,
+      matchCount: marks.length,
+    });
+
+  const clear = () => {
+    const parents = new Set();
+    for (const mark of marks) {
+      const parent = mark.parentNode;
+      if (!parent) continue;
+      parent.replaceChild(document.createTextNode(mark.textContent), mark);
+      parents.add(parent);
+    }
+    parents.forEach((parent) => parent.normalize());
+    marks = [];
+    active = -1;
+  };
+
+  const setActive = (index) => {
+    if (marks[active]) marks[active].setAttribute(MARK_ATTR, "");
+    active = index;
+    if (marks[active]) {
+      marks[active].setAttribute(MARK_ATTR, "active");
+      marks[active].scrollIntoView({ block: "center", inline: "nearest" });
+    }
+  };
+
+  const buildPattern = (query, options) => {
+    let source = query.replace(/[.*+?^${}()|[\]\\\/]/g, "\\$&");
+    if (options.wholeWord) source = `(?<![\\p{L}\\p{N}_])${source}(?![\\p{L}\\p{N}_])`;
+    return new RegExp(source, options.caseSensitive ? "gu" : "giu");
+  };
+
+  // 可见的文本节点，跨节点的匹配不做处理
+  const textNodes = () => {
+    const root = document.body || document.documentElement;
+    const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, {
+      acceptNode(node) {
+        const parent = node.parentElement;
+        if (!parent || !node.data.trim() || parent.closest(SKIP_SELECTOR)) {
+          return NodeFilter.FILTER_REJECT;
+        }
+        return parent.getClientRects().length ? NodeFilter.FILTER_ACCEPT : NodeFilter.FILTER_REJECT;
+      },
+    });
+    const nodes = [];
+    while (walker.nextNode()) nodes.push(walker.currentNode);
+    return nodes;
+  };
+
+  const highlight = (node, pattern) => {
+    const ranges = [];
+    pattern.lastIndex = 0;
+    let match;
+    while ((match = pattern.exec(node.data)) !== null) {
+      if (!match[0].length) {
+        pattern.lastIndex++;
+        continue;
+      }
+      ranges.push([match.index, match[0].length]);
+    }
+    let rest = node;
+    let offset = 0;
+    for (const [start, length] of ranges) {
+      const matched = rest.splitText(start - offset);
+      rest = matched.splitText(length);
+      offset = start + length;
+      const mark = document.createElement("mark");
+      mark.setAttribute(MARK_ATTR, "");
+      matched.parentNode.replaceChild(mark, matched);
+      mark.appendChild(matched);
+      marks.push(mark);
+    }
+  };
+
+  const find = (query, options) => {
+    clear();
+    lastQuery = query;
+    lastOptions = options;
+    if (query) {
+      if (!style.isConnected) (document.head || document.documentElement).appendChild(style);
+      const pattern = buildPattern(query, options);
+      textNodes().forEach((node) => highlight(node, pattern));
+      // 从视口中的第一个匹配开始
+      const first = marks.findIndex((mark) => mark.getBoundingClientRect().bottom >= 0);
+      if (marks.length) setActive(Math.max(first, 0));
+    }
+    report();
+  };
+
+  const step = (delta) => {
+    // 页面内容变化导致高亮丢失时重新查找
+    if (marks.some((mark) => !mark.isConnected)) {
+      find(lastQuery, lastOptions);
+      return;
+    }
+    if (marks.length) setActive((active + delta + marks.length) % marks.length);
+    report();
+  };
+
+  const stop = () => {
+    clear();
+    lastQuery = "";
+    style.remove();
+  };
+
+  Object.defineProperty(window, "__rinFind", { value: { find, step, stop } });
+})();
+"#;
+
+/// 查找选项
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FindOptions {
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+}
+
+/// 在 tab 页面中查找并高亮所有匹配，结果通过 FindResult 事件返回
+pub fn find_in_page(
+    app: &AppHandle,
+    window_label: &str,
+    tab_id: &Uuid,
+    query: &str,
+    options: FindOptions,
+) -> Result<(), String> {
+    let query = serde_json::to_string(query).map_err(|e| e.to_string())?;
+    let options = serde_json::json!({
+        "caseSensitive": options.case_sensitive,
+        "wholeWord": options.whole_word,
+    });
+    eval_find(
+        &tab_webview(app, window_label, tab_id)?,
+        &format!("find({}, {})", query, options),
+    )
+}
+
+/// 跳到下一个（forward）或上一个匹配
+pub fn find_step(
+    app: &AppHandle,
+    window_label: &str,
+    tab_id: &Uuid,
+    forward: bool,
+) -> Result<(), String> {
+    let delta = if forward { 1 } else { -1 };
+    eval_find(
+        &tab_webview(app, window_label, tab_id)?,
+        &format!("step({})", delta),
+    )
+}
+
+/// 结束查找并清除高亮
+pub fn stop_find(app: &AppHandle, window_label: &str, tab_id: &Uuid) -> Result<(), String> {
+    eval_find(&tab_webview(app, window_label, tab_id)?, "stop()")
+}
+
+fn tab_webview(app: &AppHandle, window_label: &str, tab_id: &Uuid) -> Result<Webview, String> {
+    app.get_window(window_label)
+        .ok_or("Window not found")?
+        .get_webview(&tab_id.to_string())
+        .ok_or_else(|| "WebView not found".into())
+}
+
+fn eval_find(webview: &Webview, call: &str) -> Result<(), String> {
+    webview
+        .eval(format!("window.__rinFind && window.__rinFind.{};", call))
+        .map_err(|e| e.to_string())
+}
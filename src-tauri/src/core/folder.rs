@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Folder {
+    pub id: Uuid,
+    pub space_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub index: usize,
+    pub is_collapsed: bool,
+}
+
+// 单个窗口内的 tab 文件夹，可嵌套
+#[derive(Default)]
+pub struct TabFolders {
+    folders: HashMap<Uuid, Folder>,
+}
+
+impl TabFolders {
+    pub fn get(&self, folder_id: &Uuid) -> Option<&Folder> {
+        self.folders.get(folder_id)
+    }
+
+    pub fn contains(&self, folder_id: &Uuid) -> bool {
+        self.folders.contains_key(folder_id)
+    }
+
+    /// 指定 space 下的文件夹，按 (parent, index) 排序
+    pub fn list(&self, space_id: &Uuid) -> Vec<Folder> {
+        let mut folders: Vec<Folder> = self
+            .folders
+            .values()
+            .filter(|f| f.space_id == *space_id)
+            .cloned()
+            .collect();
+        folders.sort_by_key(|f| (f.parent_id, f.index));
+        folders
+    }
+
+    pub fn all(&self) -> Vec<Folder> {
+        self.folders.values().cloned().collect()
+    }
+
+    pub fn create(
+        &mut self,
+        space_id: Uuid,
+        parent_id: Option<Uuid>,
+        name: &str,
+    ) -> Result<Folder, String> {
+        if let Some(parent_id) = parent_id {
+            let parent = self.folders.get(&parent_id).ok_or("Folder not found")?;
+            if parent.space_id != space_id {
+                return Err("Parent folder belongs to another space".into());
+            }
+        }
+        let folder = Folder {
+            id: Uuid::new_v4(),
+            space_id,
+            parent_id,
+            name: name.to_string(),
+            index: self.children(&space_id, parent_id).len(),
+            is_collapsed: false,
+        };
+        self.folders.insert(folder.id, folder.clone());
+        Ok(folder)
+    }
+
+    pub fn rename(&mut self, folder_id: &Uuid, name: &str) -> Result<(), String> {
+        let folder = self.folders.get_mut(folder_id).ok_or("Folder not found")?;
+        folder.name = name.to_string();
+        Ok(())
+    }
+
+    pub fn set_collapsed(&mut self, folder_id: &Uuid, collapsed: bool) -> Result<(), String> {
+        let folder = self.folders.get_mut(folder_id).ok_or("Folder not found")?;
+        folder.is_collapsed = collapsed;
+        Ok(())
+    }
+
+    /// 文件夹自身及所有子孙文件夹 id
+    pub fn subtree(&self, folder_id: &Uuid) -> Vec<Uuid> {
+        let mut ids = vec![*folder_id];
+        let mut cursor = 0;
+        while cursor < ids.len() {
+            let current = ids[cursor];
+            ids.extend(
+                self.folders
+                    .values()
+                    .filter(|f| f.parent_id == Some(current))
+                    .map(|f| f.id),
+            );
+            cursor += 1;
+        }
+        ids
+    }
+
+    /// 删除整个子树
+    pub fn remove_subtree(&mut self, folder_id: &Uuid) -> Vec<Uuid> {
+        let ids = self.subtree(folder_id);
+        for id in ids.iter() {
+            self.folders.remove(id);
+        }
+        self.reindex();
+        ids
+    }
+
+    /// 只删除文件夹本身，子文件夹上移到其父级并占据原位置
+    pub fn remove_lift_children(&mut self, folder_id: &Uuid) -> Option<Folder> {
+        let folder = self.folders.remove(folder_id)?;
+
+        let mut siblings = self.children(&folder.space_id, folder.parent_id);
+        siblings.sort_by_key(|f| f.index);
+        let mut children = self.children(&folder.space_id, Some(*folder_id));
+        children.sort_by_key(|f| f.index);
+
+        let split = siblings.partition_point(|f| f.index < folder.index);
+        let order: Vec<Uuid> = siblings[..split]
+            .iter()
+            .chain(children.iter())
+            .chain(siblings[split..].iter())
+            .map(|f| f.id)
+            .collect();
+        for (index, id) in order.iter().enumerate() {
+            if let Some(f) = self.folders.get_mut(id) {
+                f.parent_id = folder.parent_id;
+                f.index = index;
+            }
+        }
+        Some(folder)
+    }
+
+    /// 用会话中的文件夹替换当前列表
+    pub fn restore(&mut self, folders: Vec<Folder>) {
+        self.folders = folders.into_iter().map(|f| (f.id, f)).collect();
+        // 丢弃父级缺失的文件夹
+        loop {
+            let orphan_ids: Vec<Uuid> = self
+                .folders
+                .values()
+                .filter(|f| f.parent_id.is_some_and(|p| !self.folders.contains_key(&p)))
+                .map(|f| f.id)
+                .collect();
+            if orphan_ids.is_empty() {
+                break;
+            }
+            for id in orphan_ids {
+                self.folders.remove(&id);
+            }
+        }
+        self.reindex();
+    }
+
+    /// 删除指定 space 下的所有文件夹
+    pub fn remove_space(&mut self, space_id: &Uuid) {
+        self.folders.retain(|_, f| f.space_id != *space_id);
+    }
+
+    fn children(&self, space_id: &Uuid, parent_id: Option<Uuid>) -> Vec<&Folder> {
+        self.folders
+            .values()
+            .filter(|f| f.space_id == *space_id && f.parent_id == parent_id)
+            .collect()
+    }
+
+    /// 同级文件夹按 index 重新压缩
+    fn reindex(&mut self) {
+        let mut folder_list: Vec<_> = self.folders.values_mut().collect();
+        folder_list.sort_by_key(|f| (f.space_id, f.parent_id, f.index));
+        let mut group = None;
+        let mut next_index = 0;
+        for folder in folder_list {
+            let key = (folder.space_id, folder.parent_id);
+            if group != Some(key) {
+                group = Some(key);
+                next_index = 0;
+            }
+            folder.index = next_index;
+            next_index += 1;
+        }
+    }
+}
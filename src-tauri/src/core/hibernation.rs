@@ -0,0 +1,39 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::core::settings;
+use crate::core::tab::TabManager;
+
+// 默认闲置 30 分钟后休眠
+pub const DEFAULT_HIBERNATE_AFTER_SECS: u64 = 30 * 60;
+// 闲置检查间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 当前的休眠闲置时长，关闭休眠时为 None
+pub fn hibernate_after() -> Option<Duration> {
+    match settings::settings().hibernate_after_secs {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// 设置休眠闲置时长（秒）并保存到设置，0 关闭休眠
+pub fn set_hibernate_after(app: &AppHandle, secs: u64) -> anyhow::Result<()> {
+    settings::update_settings(app, |s| s.hibernate_after_secs = secs)?;
+    Ok(())
+}
+
+/// 启动休眠检查线程，定期卸载闲置后台 tab 的 webview
+pub fn start_hibernation(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        let Some(idle) = hibernate_after() else {
+            continue;
+        };
+        let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+        // 被休眠的 tab 通过 Hibernated 事件通知前端
+        tab_manager.lock().unwrap().hibernate_idle_tabs(&app, idle);
+    });
+}
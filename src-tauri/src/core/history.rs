@@ -0,0 +1,150 @@
+use serde::Serialize;
+
+// 单个 tab 最多保留的历史记录数量
+const MAX_HISTORY_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: String,
+}
+
+/// tab 的浏览历史，供后退菜单使用
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabHistoryInfo {
+    pub entries: Vec<HistoryEntry>,
+    pub current_index: usize,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+}
+
+/// 跳转到历史记录的方式
+pub enum Traversal {
+    // 在 webview 自身的历史中前进/后退若干步
+    Go(i64),
+    // webview 已重建，原生历史中没有该记录，直接加载地址
+    Load(String),
+}
+
+// 单个 tab 的导航历史，由 webview 的导航与页面加载回调维护
+// [native_start, native_end] 为当前 webview 原生历史中可达的记录，
+// webview 重建（休眠、延迟加载）后原生历史只剩当前页
+#[derive(Debug, Clone, Default)]
+pub struct TabHistory {
+    entries: Vec<HistoryEntry>,
+    current: usize,
+    native_start: usize,
+    native_end: usize,
+    // 由我们发起、尚未完成的历史跳转
+    traversal: Option<usize>,
+}
+
+impl TabHistory {
+    pub fn can_go_back(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.current + 1 < self.entries.len()
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn info(&self) -> TabHistoryInfo {
+        TabHistoryInfo {
+            entries: self.entries.clone(),
+            current_index: self.current,
+            can_go_back: self.can_go_back(),
+            can_go_forward: self.can_go_forward(),
+        }
+    }
+
+    /// 页面开始导航，与正在进行的历史跳转目标不同则视为新的导航
+    pub fn navigation_requested(&mut self, url: &str) {
+        if let Some(target) = self.traversal {
+            if self.entries.get(target).is_some_and(|e| e.url != url) {
+                self.traversal = None;
+            }
+        }
+    }
+
+    /// 页面加载完成，更新当前位置或追加新记录
+    pub fn commit(&mut self, url: &str) {
+        if let Some(target) = self.traversal.take() {
+            if self.entries.get(target).is_some_and(|e| e.url == url) {
+                if !(self.native_start..=self.native_end).contains(&target) {
+                    self.native_start = target;
+                    self.native_end = target;
+                }
+                self.current = target;
+                return;
+            }
+        }
+
+        // 刷新
+        if self.entries.get(self.current).is_some_and(|e| e.url == url) {
+            return;
+        }
+        // 页面内触发的前进/后退（手势、快捷键、history API）
+        if self.current > self.native_start && self.entries[self.current - 1].url == url {
+            self.current -= 1;
+            return;
+        }
+        if self.current < self.native_end && self.entries[self.current + 1].url == url {
+            self.current += 1;
+            return;
+        }
+
+        // 新的导航丢弃当前位置之后的记录
+        self.entries.truncate(self.current + 1);
+        self.entries.push(HistoryEntry {
+            url: url.to_string(),
+            title: String::new(),
+        });
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let overflow = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..overflow);
+            self.native_start = self.native_start.saturating_sub(overflow);
+        }
+        self.current = self.entries.len() - 1;
+        self.native_end = self.current;
+    }
+
+    /// 页面用 history.replaceState 替换了当前地址
+    pub fn replace_current(&mut self, url: &str) {
+        if self.entries.is_empty() {
+            self.commit(url);
+        } else {
+            self.entries[self.current].url = url.to_string();
+        }
+    }
+
+    /// 更新当前记录的标题
+    pub fn set_title(&mut self, title: &str) {
+        if let Some(entry) = self.entries.get_mut(self.current) {
+            entry.title = title.to_string();
+        }
+    }
+
+    /// 开始跳转到指定记录，返回跳转方式
+    pub fn traverse(&mut self, index: usize) -> Result<Traversal, String> {
+        let entry = self.entries.get(index).ok_or("History entry not found")?;
+        self.traversal = Some(index);
+        if (self.native_start..=self.native_end).contains(&index) {
+            Ok(Traversal::Go(index as i64 - self.current as i64))
+        } else {
+            Ok(Traversal::Load(entry.url.clone()))
+        }
+    }
+
+    /// webview 重建后，原生历史只剩当前页
+    pub fn webview_recreated(&mut self) {
+        self.native_start = self.current;
+        self.native_end = self.current;
+        self.traversal = None;
+    }
+}
@@ -64,6 +64,19 @@ pub struct SidebarManager {
     map: HashMap<String, SidebarState>,
 }
 
+// 侧栏宽度范围
+const MIN_SIDEBAR_WIDTH: f64 = 150.0;
+const MAX_SIDEBAR_WIDTH: f64 = 480.0;
+
+/// 侧栏宽度限制在合理范围内，无效值使用默认宽度
+pub fn clamp_sidebar_width(width: f64) -> f64 {
+    if width.is_finite() {
+        width.clamp(MIN_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH)
+    } else {
+        SidebarManager::DEFAULT_WIDTH
+    }
+}
+
 impl SidebarManager {
     const DEFAULT_WIDTH: f64 = 205.0;
 
@@ -74,6 +87,9 @@ impl SidebarManager {
     // }
 
     pub fn set(&mut self, window_label: &str, state: SidebarState) {
+        let state = SidebarState {
+            width: clamp_sidebar_width(state.width),
+        };
         self.map.insert(window_label.to_string(), state);
     }
 
@@ -84,9 +100,13 @@ impl SidebarManager {
             .unwrap_or(Self::DEFAULT_WIDTH)
     }
 
+    pub fn remove(&mut self, window_label: &str) {
+        self.map.remove(window_label);
+    }
+
     pub fn update_width(&mut self, window_label: &str, width: f64) {
         if let Some(state) = self.map.get_mut(window_label) {
-            state.width = width;
+            state.width = clamp_sidebar_width(width);
         }
     }
 }
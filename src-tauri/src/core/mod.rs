@@ -1,5 +1,22 @@
 pub mod ai_overlay;
+pub mod bridge;
+pub mod closed_tab;
+#[cfg(target_os = "macos")]
+pub mod crash;
+pub mod favicon;
+pub mod find;
+pub mod folder;
+pub mod hibernation;
+pub mod history;
 pub mod layout;
+pub mod popup;
+pub mod private_data;
+pub mod session;
+pub mod settings;
 pub mod setup;
+pub mod space;
+pub mod split;
 pub mod tab;
+pub mod tab_event;
 pub mod webview;
+pub mod zoom;
@@ -0,0 +1,225 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::webview::NewWindowFeatures;
+use tauri::{
+    AppHandle, Manager, Url, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent,
+};
+use uuid::Uuid;
+
+use crate::core::settings;
+
+/// 页面请求打开新窗口（target=_blank、window.open）时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NewWindowAction {
+    // 指定了尺寸的弹窗用浮动窗口打开，其余用 tab 打开
+    #[default]
+    Auto,
+    // 在打开者旁边新建 tab
+    Tab,
+    // 浮动窗口，保留 window.opener，用于 OAuth 等登录弹窗
+    Popup,
+}
+
+/// 按目标地址域名指定处理方式的规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewWindowRule {
+    // 匹配该域名及其子域名
+    pub host: String,
+    pub action: NewWindowAction,
+}
+
+// 手势脚本中的令牌占位符
+const GESTURE_TOKEN_PLACEHOLDER: &str = "__RIN_GESTURE_TOKEN__";
+
+// 桥接地址中表示用户手势的 host：rin-bridge://gesture?t=<token>
+pub const GESTURE_HOST: &str = "gesture";
+
+// 用户手势后允许弹窗的时长
+const GESTURE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// 注入到所有 frame：只记录真实的用户输入，是否放行弹窗由 on_new_window 决定。
+// 上报用的 iframe 放在 closed shadow root 中，页面脚本无法读取令牌
+const GESTURE_SCRIPT: &str = r#"
+(function () {
+  if (window.__rinGesture) return;
+  Object.defineProperty(window, "__rinGesture", { value: true });
+
+  const token = "__RIN_GESTURE_TOKEN__";
+  const apply = Reflect.apply;
+  const createElement = Document.prototype.createElement;
+  const attachShadow = Element.prototype.attachShadow;
+  const appendChild = Node.prototype.appendChild;
+  const remove = Element.prototype.remove;
+  const setSrc = Object.getOwnPropertyDescriptor(HTMLIFrameElement.prototype, "src").set;
+  const setTimeout = window.setTimeout;
+
+  let lastReport = 0;
+  const report = () => {
+    const now = Date.now();
+    if (now - lastReport < 500) return;
+    lastReport = now;
+    const root = document.documentElement;
+    if (!root) return;
+    const host = apply(createElement, document, ["span"]);
+    const shadow = apply(attachShadow, host, [{ mode: "closed" }]);
+    const frame = apply(createElement, document, ["iframe"]);
+    apply(setSrc, frame, ["rin-bridge://gesture?t=" + token]);
+    apply(appendChild, shadow, [frame]);
+    apply(appendChild, root, [host]);
+    setTimeout(() => apply(remove, host, []), 1000);
+  };
+  for (const type of ["pointerdown", "mousedown", "keydown", "touchstart"]) {
+    window.addEventListener(type, (event) => event.isTrusted && report(), true);
+  }
+})();
+"#;
+
+// tab -> 最近一次用户手势的时间
+static LAST_GESTURE: Lazy<Mutex<HashMap<Uuid, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// 每个 tab 最多记录的被拦截弹窗数量
+const MAX_BLOCKED_POPUPS: usize = 20;
+
+/// 被拦截的弹窗
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedPopup {
+    pub url: String,
+    // 发起弹窗的页面 origin
+    pub origin: String,
+}
+
+/// 记录被拦截的弹窗，超出上限时丢弃最早的
+pub fn push_blocked(list: &mut Vec<BlockedPopup>, popup: BlockedPopup) {
+    list.push(popup);
+    if list.len() > MAX_BLOCKED_POPUPS {
+        list.remove(0);
+    }
+}
+
+/// 生成 tab 的手势脚本，token 为该 webview 独有的令牌
+pub fn gesture_script(token: &str) -> String {
+    GESTURE_SCRIPT.replace(GESTURE_TOKEN_PLACEHOLDER, token)
+}
+
+/// 记录 tab 页面上的用户手势
+pub fn record_gesture(tab_id: Uuid) {
+    LAST_GESTURE.lock().unwrap().insert(tab_id, Instant::now());
+}
+
+/// 判断是否允许 tab 打开新窗口：站点在例外中，或近期有用户手势，一次手势只放行一个弹窗
+pub fn allow_new_window(tab_id: Uuid, origin: &str) -> bool {
+    if settings::settings()
+        .popup_exceptions
+        .iter()
+        .any(|o| o == origin)
+    {
+        return true;
+    }
+    LAST_GESTURE
+        .lock()
+        .unwrap()
+        .remove(&tab_id)
+        .is_some_and(|at| at.elapsed() <= GESTURE_TIMEOUT)
+}
+
+/// 始终允许 origin 弹窗
+pub fn allow_origin(app: &AppHandle, origin: &str) -> anyhow::Result<()> {
+    settings::update_settings(app, |s| {
+        if !s.popup_exceptions.iter().any(|o| o == origin) {
+            s.popup_exceptions.push(origin.to_string());
+        }
+    })?;
+    Ok(())
+}
+
+/// 移除 origin 的弹窗例外
+pub fn remove_origin(app: &AppHandle, origin: &str) -> anyhow::Result<()> {
+    settings::update_settings(app, |s| s.popup_exceptions.retain(|o| o != origin))?;
+    Ok(())
+}
+
+// 浮动弹窗 label -> 打开它的 tab
+static POPUPS: Lazy<Mutex<HashMap<String, Uuid>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 根据用户规则与窗口特性决定用 tab 还是浮动窗口打开
+pub fn resolve_action(url: &Url, features: &NewWindowFeatures) -> NewWindowAction {
+    let settings = settings::settings();
+    let host = url.host_str().unwrap_or_default();
+    let action = settings
+        .new_window_rules
+        .iter()
+        .find(|rule| host_matches(host, &rule.host))
+        .map_or(settings.new_window_action, |rule| rule.action);
+
+    match action {
+        NewWindowAction::Auto if features.size().is_some() => NewWindowAction::Popup,
+        NewWindowAction::Auto => NewWindowAction::Tab,
+        action => action,
+    }
+}
+
+/// 创建浮动弹窗，webview 由打开者的配置创建，window.opener 可用
+pub fn open_popup(
+    app: &AppHandle,
+    window_label: &str,
+    opener_tab_id: Uuid,
+    url: &Url,
+    features: NewWindowFeatures,
+) -> tauri::Result<WebviewWindow> {
+    let popup_label = format!("popup-{}", Uuid::new_v4().simple());
+    let mut builder =
+        WebviewWindowBuilder::new(app, &popup_label, WebviewUrl::External(url.clone()))
+            .window_features(features)
+            .title(url.as_str());
+    // 浮在打开者窗口之上
+    if let Some(parent) = app.get_webview_window(window_label) {
+        builder = builder.parent(&parent)?;
+    }
+    let popup = builder.build()?;
+
+    POPUPS
+        .lock()
+        .unwrap()
+        .insert(popup_label.clone(), opener_tab_id);
+    popup.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            POPUPS.lock().unwrap().remove(&popup_label);
+        }
+    });
+
+    Ok(popup)
+}
+
+/// 关闭 tab 打开的浮动弹窗，并丢弃其手势记录
+pub fn close_popups_of(app: &AppHandle, tab_ids: &[Uuid]) {
+    LAST_GESTURE
+        .lock()
+        .unwrap()
+        .retain(|id, _| !tab_ids.contains(id));
+    let labels: Vec<String> = POPUPS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, opener)| tab_ids.contains(opener))
+        .map(|(label, _)| label.clone())
+        .collect();
+    for label in labels {
+        if let Some(popup) = app.get_webview_window(&label) {
+            let _ = popup.close();
+        }
+    }
+}
+
+fn host_matches(host: &str, rule_host: &str) -> bool {
+    let rule_host = rule_host
+        .trim()
+        .trim_start_matches("*.")
+        .to_ascii_lowercase();
+    !rule_host.is_empty() && (host == rule_host || host.ends_with(&format!(".{}", rule_host)))
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+#[cfg(not(target_os = "macos"))]
+use std::{fs, io, path::PathBuf};
+use tauri::webview::WebviewBuilder;
+use tauri::{AppHandle, Wry};
+use uuid::Uuid;
+
+// 窗口销毁后等待 webview 释放数据存储再删除
+const REMOVE_DELAY: Duration = Duration::from_secs(1);
+
+/// 让 webview 使用隐私窗口共享的数据存储，同一窗口的 tab 共用 cookie 与登录状态
+pub fn apply(builder: WebviewBuilder<Wry>, store_id: &Uuid) -> WebviewBuilder<Wry> {
+    #[cfg(target_os = "macos")]
+    {
+        if supports_data_store_identifier() {
+            builder.data_store_identifier(*store_id.as_bytes())
+        } else {
+            // macOS 14 以下不支持指定数据存储，退回每个 webview 独立的无痕存储，不与普通窗口共享
+            builder.incognito(true)
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        builder.data_directory(data_dir(store_id))
+    }
+}
+
+/// WKWebsiteDataStore 是否支持按标识创建数据存储（macOS 14+）
+#[cfg(target_os = "macos")]
+fn supports_data_store_identifier() -> bool {
+    objc2::runtime::AnyClass::get(c"WKWebsiteDataStore").is_some_and(|class| {
+        class
+            .class_method(objc2::sel!(dataStoreForIdentifier:))
+            .is_some()
+    })
+}
+
+/// 隐私窗口关闭后删除其数据存储
+pub fn remove(app: &AppHandle, store_id: Uuid) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(REMOVE_DELAY);
+        remove_store(&app, store_id);
+    });
+}
+
+/// 启动时清理上次异常退出遗留的隐私数据，需在创建隐私窗口之前调用
+#[cfg(target_os = "macos")]
+pub fn remove_stale(app: &AppHandle) {
+    // 普通窗口使用默认数据存储，带标识的存储都属于隐私窗口
+    let handle = app.clone();
+    let result = app.fetch_data_store_identifiers(move |identifiers| {
+        for identifier in identifiers {
+            remove(&handle, Uuid::from_bytes(identifier));
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("[Private:clean] Failed to list data stores: {}", e);
+    }
+}
+
+/// 启动时清理上次异常退出遗留的隐私数据，需在创建隐私窗口之前调用
+#[cfg(not(target_os = "macos"))]
+pub fn remove_stale(_app: &AppHandle) {
+    let root = root_dir();
+    if let Err(e) = fs::remove_dir_all(&root) {
+        if e.kind() != io::ErrorKind::NotFound {
+            eprintln!("[Private:clean] Failed to remove {:?}: {}", root, e);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn remove_store(app: &AppHandle, store_id: Uuid) {
+    let result = app.remove_data_store(*store_id.as_bytes(), move |result| {
+        if let Err(e) = result {
+            eprintln!(
+                "[Private:remove] Failed to remove store {}: {}",
+                store_id, e
+            );
+        }
+    });
+    if let Err(e) = result {
+        eprintln!(
+            "[Private:remove] Failed to remove store {}: {}",
+            store_id, e
+        );
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn remove_store(_app: &AppHandle, store_id: Uuid) {
+    let dir = data_dir(&store_id);
+    if let Err(e) = fs::remove_dir_all(&dir) {
+        if e.kind() != io::ErrorKind::NotFound {
+            eprintln!("[Private:remove] Failed to remove {:?}: {}", dir, e);
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn root_dir() -> PathBuf {
+    std::env::temp_dir().join("rin-private")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn data_dir(store_id: &Uuid) -> PathBuf {
+    root_dir().join(store_id.simple().to_string())
+}
@@ -0,0 +1,298 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use crate::core::folder::Folder;
+use crate::core::layout::{get_sidebar_width, get_window_scale_factor};
+use crate::core::space::Space;
+use crate::core::tab::{Tab, TabManager};
+
+const SESSION_FILE: &str = "session.json";
+const SESSION_BACKUP_FILE: &str = "session.json.bak";
+const SESSION_TMP_FILE: &str = "session.json.tmp";
+// 固定 tab 单独保存，会话无法恢复时仍可恢复固定 tab
+const PINNED_FILE: &str = "pinned.json";
+const PINNED_TMP_FILE: &str = "pinned.json.tmp";
+const SESSION_VERSION: u32 = 1;
+// 自动保存检查间隔
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 会话快照，写入 app data 目录
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSnapshot {
+    pub version: u32,
+    #[serde(default)]
+    pub spaces: Vec<Space>,
+    pub windows: Vec<WindowSession>,
+}
+
+impl SessionSnapshot {
+    pub fn window(&self, window_label: &str) -> Option<&WindowSession> {
+        self.windows.iter().find(|w| w.label == window_label)
+    }
+
+    /// 只保留固定 tab 的快照
+    fn pinned_only(&self) -> SessionSnapshot {
+        let windows = self
+            .windows
+            .iter()
+            .map(|w| WindowSession {
+                active_tab_id: None,
+                tabs: w.tabs.iter().filter(|t| t.is_pinned).cloned().collect(),
+                folders: Vec::new(),
+                ..w.clone()
+            })
+            .collect();
+        SessionSnapshot {
+            version: self.version,
+            spaces: self.spaces.clone(),
+            windows,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowSession {
+    pub label: String,
+    pub geometry: Option<WindowGeometry>,
+    pub sidebar_width: f64,
+    pub active_tab_id: Option<Uuid>,
+    pub tabs: Vec<Tab>,
+    #[serde(default)]
+    pub folders: Vec<Folder>,
+}
+
+/// 窗口位置与尺寸（逻辑像素），最大化、全屏时为还原后的位置与尺寸
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+}
+
+// 是否有未保存的变更
+static SESSION_DIRTY: AtomicBool = AtomicBool::new(false);
+
+// 最后一个窗口关闭或退出时冻结，之后不再写盘，保留关闭前的会话
+static SESSION_FROZEN: AtomicBool = AtomicBool::new(false);
+
+// 串行化写盘，避免自动保存与退出保存同时写临时文件；只保护写盘，不能在持有时查询窗口
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+// 快照序号：较早生成的快照不会覆盖已写入的较新快照
+static NEXT_SNAPSHOT_SEQ: AtomicU64 = AtomicU64::new(1);
+static WRITTEN_SNAPSHOT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+// 最近一次写入的快照，窗口已销毁时用于保留其几何信息
+static LAST_SNAPSHOT: Lazy<Mutex<Option<SessionSnapshot>>> = Lazy::new(|| Mutex::new(None));
+
+/// 标记会话已变更，由自动保存线程写盘
+pub fn mark_dirty() {
+    SESSION_DIRTY.store(true, Ordering::Relaxed);
+}
+
+/// 停止保存会话，窗口陆续销毁时不会覆盖已保存的完整会话
+pub fn freeze() {
+    SESSION_FROZEN.store(true, Ordering::Relaxed);
+}
+
+/// 重新打开普通窗口后恢复保存会话
+pub fn unfreeze() {
+    SESSION_FROZEN.store(false, Ordering::Relaxed);
+    mark_dirty();
+}
+
+/// 启动自动保存线程
+pub fn start_autosave(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(AUTOSAVE_INTERVAL);
+        if SESSION_DIRTY.swap(false, Ordering::Relaxed) {
+            if let Err(e) = save_session(&app) {
+                eprintln!("[Session:save] Failed to save session: {:#}", e);
+            }
+        }
+    });
+}
+
+/// 读取会话，主文件损坏时回退到上一次完好的备份，再退回到仅恢复固定 tab
+pub fn load_session(app: &AppHandle) -> Option<SessionSnapshot> {
+    let dir = session_dir(app).ok()?;
+
+    for file in [SESSION_FILE, SESSION_BACKUP_FILE, PINNED_FILE] {
+        let path = dir.join(file);
+        if !path.exists() {
+            continue;
+        }
+        match read_snapshot(&path) {
+            Ok(snapshot) => {
+                *LAST_SNAPSHOT.lock().unwrap() = Some(snapshot.clone());
+                return Some(snapshot);
+            }
+            Err(e) => eprintln!("[Session:load] Ignoring {}: {:#}", path.display(), e),
+        }
+    }
+    None
+}
+
+/// 立即保存当前会话
+pub fn save_session(app: &AppHandle) -> Result<()> {
+    if SESSION_FROZEN.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    // 窗口查询在非主线程调用时要等待主线程响应，而主线程也会保存会话，须在加锁前完成
+    let seq = NEXT_SNAPSHOT_SEQ.fetch_add(1, Ordering::Relaxed);
+    let snapshot = build_snapshot(app);
+
+    let _guard = SAVE_LOCK.lock().unwrap();
+    if SESSION_FROZEN.load(Ordering::Relaxed) || seq <= WRITTEN_SNAPSHOT_SEQ.load(Ordering::Relaxed)
+    {
+        return Ok(());
+    }
+    let dir = session_dir(app)?;
+    fs::create_dir_all(&dir).context("Failed to create app data dir")?;
+
+    // 先写临时文件，避免写到一半的主文件
+    let tmp_path = dir.join(SESSION_TMP_FILE);
+    let data = serde_json::to_vec_pretty(&snapshot)?;
+    write_synced(&tmp_path, &data)?;
+
+    // 当前主文件完好时轮换为备份
+    let session_path = dir.join(SESSION_FILE);
+    if read_snapshot(&session_path).is_ok() {
+        fs::rename(&session_path, dir.join(SESSION_BACKUP_FILE))
+            .context("Failed to rotate session backup")?;
+    }
+    fs::rename(&tmp_path, &session_path).context("Failed to replace session file")?;
+
+    let pinned_tmp_path = dir.join(PINNED_TMP_FILE);
+    let pinned_data = serde_json::to_vec_pretty(&snapshot.pinned_only())?;
+    write_synced(&pinned_tmp_path, &pinned_data)?;
+    fs::rename(&pinned_tmp_path, dir.join(PINNED_FILE))
+        .context("Failed to replace pinned tabs file")?;
+
+    *LAST_SNAPSHOT.lock().unwrap() = Some(snapshot);
+    WRITTEN_SNAPSHOT_SEQ.store(seq, Ordering::Relaxed);
+    Ok(())
+}
+
+fn build_snapshot(app: &AppHandle) -> SessionSnapshot {
+    let last = LAST_SNAPSHOT.lock().unwrap().clone();
+
+    // 先拷贝 tab 状态并释放锁，窗口查询需要主线程响应
+    let (spaces, tab_states): (Vec<_>, Vec<_>) = {
+        let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+        let tm = tab_manager.lock().unwrap();
+        let tab_states = tm
+            .window_labels()
+            .into_iter()
+            // 隐私窗口不保存
+            .filter(|label| !tm.is_private_window(label))
+            .map(|label| {
+                let active_tab_id = tm.get_active_tab_id(&label);
+                let tabs = tm.get_all_tabs(&label);
+                let folders = tm.get_all_folders(&label);
+                (label, active_tab_id, tabs, folders)
+            })
+            .collect();
+        (tm.spaces().list(), tab_states)
+    };
+
+    let windows = tab_states
+        .into_iter()
+        .map(|(label, active_tab_id, tabs, folders)| {
+            let last_geometry = last
+                .as_ref()
+                .and_then(|s| s.window(&label))
+                .and_then(|w| w.geometry);
+            let geometry = get_window_geometry(app, &label, last_geometry).or(last_geometry);
+            WindowSession {
+                geometry,
+                sidebar_width: get_sidebar_width(&label),
+                active_tab_id,
+                tabs,
+                folders,
+                label,
+            }
+        })
+        .collect();
+
+    SessionSnapshot {
+        version: SESSION_VERSION,
+        spaces,
+        windows,
+    }
+}
+
+fn get_window_geometry(
+    app: &AppHandle,
+    window_label: &str,
+    last: Option<WindowGeometry>,
+) -> Option<WindowGeometry> {
+    let window = app.get_window(window_label)?;
+    let maximized = window.is_maximized().unwrap_or(false);
+    let fullscreen = window.is_fullscreen().unwrap_or(false);
+    // 最大化、全屏、最小化时沿用之前的普通位置与尺寸，还原后回到该位置
+    if maximized || fullscreen || window.is_minimized().unwrap_or(false) {
+        if let Some(last) = last {
+            return Some(WindowGeometry {
+                maximized,
+                fullscreen,
+                ..last
+            });
+        }
+    }
+
+    let scale_factor = get_window_scale_factor(app, window_label)?;
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+
+    Some(WindowGeometry {
+        x: position.x as f64 / scale_factor,
+        y: position.y as f64 / scale_factor,
+        width: size.width as f64 / scale_factor,
+        height: size.height as f64 / scale_factor,
+        maximized,
+        fullscreen,
+    })
+}
+
+fn session_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .context("Failed to resolve app data dir")
+}
+
+fn read_snapshot(path: &Path) -> Result<SessionSnapshot> {
+    let data = fs::read(path).context("Failed to read session file")?;
+    let snapshot: SessionSnapshot =
+        serde_json::from_slice(&data).context("Failed to parse session file")?;
+    if snapshot.version > SESSION_VERSION {
+        anyhow::bail!("Unsupported session version {}", snapshot.version);
+    }
+    Ok(snapshot)
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = fs::File::create(path).context("Failed to create session file")?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    Ok(())
+}
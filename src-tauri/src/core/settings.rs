@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::{AppHandle, Manager};
+
+use crate::core::hibernation::DEFAULT_HIBERNATE_AFTER_SECS;
+use crate::core::popup::{NewWindowAction, NewWindowRule};
+
+const SETTINGS_FILE: &str = "settings.json";
+const SETTINGS_TMP_FILE: &str = "settings.json.tmp";
+
+/// 关闭活跃 tab 后激活哪个 tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseTabActivation {
+    // 最近使用过的 tab
+    #[default]
+    Mru,
+    // 位置相邻的 tab
+    Neighbor,
+}
+
+/// 用户设置，保存在 app data 目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub close_tab_activation: CloseTabActivation,
+    // 按站点（origin）记住的缩放比例
+    pub site_zoom: HashMap<String, f64>,
+    // 页面请求打开新窗口时的默认处理方式
+    pub new_window_action: NewWindowAction,
+    // 按域名覆盖默认处理方式，先匹配的优先
+    pub new_window_rules: Vec<NewWindowRule>,
+    // 允许非用户手势弹窗的站点（origin）
+    pub popup_exceptions: Vec<String>,
+    // 后台 tab 闲置多久（秒）后休眠，0 表示关闭休眠
+    pub hibernate_after_secs: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            close_tab_activation: CloseTabActivation::default(),
+            site_zoom: HashMap::new(),
+            new_window_action: NewWindowAction::default(),
+            new_window_rules: Vec::new(),
+            popup_exceptions: Vec::new(),
+            hibernate_after_secs: DEFAULT_HIBERNATE_AFTER_SECS,
+        }
+    }
+}
+
+static SETTINGS: Lazy<RwLock<Settings>> = Lazy::new(|| RwLock::new(Settings::default()));
+
+/// 当前设置
+pub fn settings() -> Settings {
+    SETTINGS.read().unwrap().clone()
+}
+
+/// 启动时读取设置，文件缺失或损坏时使用默认值
+pub fn load_settings(app: &AppHandle) {
+    let path = match settings_dir(app) {
+        Ok(dir) => dir.join(SETTINGS_FILE),
+        Err(e) => {
+            eprintln!("[Settings:load] {:#}", e);
+            return;
+        }
+    };
+    if !path.exists() {
+        return;
+    }
+    let loaded = fs::read(&path)
+        .context("Failed to read settings file")
+        .and_then(|data| serde_json::from_slice(&data).context("Failed to parse settings file"));
+    match loaded {
+        Ok(settings) => *SETTINGS.write().unwrap() = settings,
+        Err(e) => eprintln!("[Settings:load] Ignoring {}: {:#}", path.display(), e),
+    }
+}
+
+/// 修改设置并立即写盘，返回修改后的设置
+pub fn update_settings<F>(app: &AppHandle, f: F) -> Result<Settings>
+where
+    F: FnOnce(&mut Settings),
+{
+    let mut settings = SETTINGS.write().unwrap();
+    f(&mut settings);
+
+    let dir = settings_dir(app)?;
+    fs::create_dir_all(&dir).context("Failed to create app data dir")?;
+    let tmp_path = dir.join(SETTINGS_TMP_FILE);
+    fs::write(&tmp_path, serde_json::to_vec_pretty(&*settings)?)
+        .context("Failed to write settings file")?;
+    fs::rename(&tmp_path, dir.join(SETTINGS_FILE)).context("Failed to replace settings file")?;
+
+    Ok(settings.clone())
+}
+
+fn settings_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .context("Failed to resolve app data dir")
+}
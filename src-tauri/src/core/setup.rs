@@ -3,13 +3,26 @@ use anyhow::{Context, Result};
 use std::sync::{Arc, Mutex};
 use tauri::menu::{MenuBuilder, SubmenuBuilder};
 use tauri::{
-    window::Effect, window::EffectsBuilder, App, LogicalPosition, Manager, TitleBarStyle,
-    WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent,
+    window::Effect, window::EffectsBuilder, App, AppHandle, LogicalPosition, Manager,
+    TitleBarStyle, WebviewUrl, WebviewWindow, WebviewWindowBuilder, WindowEvent,
 };
+use uuid::Uuid;
 
+use crate::core::hibernation;
 use crate::core::layout::SidebarState;
+use crate::core::private_data;
+use crate::core::session::{self, WindowGeometry, WindowSession};
+use crate::core::settings;
 use crate::core::tab::TabManager;
 
+const DEFAULT_WINDOW_LABEL: &str = "main";
+pub const DEFAULT_SIDEBAR_WIDTH: f64 = 205.0;
+// 恢复的窗口在显示器内可见的最小宽高，不足时重新居中
+const MIN_VISIBLE_SIZE: f64 = 100.0;
+// 新窗口默认打开的页面
+const NEW_TAB_URL: &str = "https://www.google.com.hk/";
+const NEW_TAB_NAME: &str = "New Tab";
+
 /// setup
 pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
     if cfg!(debug_assertions) {
@@ -20,20 +33,16 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
         )?;
     }
 
-    let main_window = window_init(app)?;
-    let window_label = main_window.label().to_string();
-    let handle_clone = app.handle().clone();
-
-    main_window.on_window_event(move |event| {
-        if let WindowEvent::Resized(_size) = event {
-            let tab_manager = handle_clone.state::<Arc<Mutex<TabManager>>>();
-            let tm = tab_manager.lock().unwrap();
-            tm.tab_resized(&handle_clone, &window_label);
-        }
-    });
+    settings::load_settings(app.handle());
+    private_data::remove_stale(app.handle());
+    window_init(app)?;
+    session::start_autosave(app.handle());
+    hibernation::start_hibernation(app.handle());
 
     let le_menu = SubmenuBuilder::new(app, "Rin")
         .text("open", "Open")
+        .text("new_window", "New Window")
+        .text("new_private_window", "New Private Window")
         .text("quit", "Quit")
         .build()?;
     let menu = MenuBuilder::new(app).items(&[&le_menu]).build()?;
@@ -45,8 +54,19 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
             "open" => {
                 println!("open event");
             }
+            "new_window" | "new_private_window" => {
+                let private = event.id().0 == "new_private_window";
+                if let Err(e) = open_new_window(app_handle, None, private) {
+                    eprintln!("[Window:new] Failed to open window: {:#}", e);
+                }
+            }
             "quit" => {
                 println!("quit event");
+                // 逐个关闭窗口会依次清理窗口状态，先保存完整会话并停止写盘
+                if let Err(e) = session::save_session(app_handle) {
+                    eprintln!("[Session:save] Failed to save session: {:#}", e);
+                }
+                session::freeze();
                 for window in app_handle.windows().values() {
                     let _ = window.close();
                 }
@@ -59,35 +79,66 @@ pub fn init(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
-fn window_init(app: &App) -> tauri::Result<WebviewWindow> {
-    const DEFAULT_WINDOW_LABEL: &str = "main";
+fn window_init(app: &App) -> tauri::Result<()> {
+    // 读取上次会话，恢复其中所有仍有 tab 的窗口
+    let snapshot = session::load_session(app.handle());
+    let window_sessions: Vec<WindowSession> = snapshot
+        .iter()
+        .flat_map(|s| s.windows.iter())
+        .filter(|w| !w.tabs.is_empty())
+        .cloned()
+        .collect();
 
-    // 构建窗口视觉特效
-    let effects = EffectsBuilder::new()
-        .effects(vec![Effect::Mica, Effect::Acrylic, Effect::HudWindow])
-        .radius(12.0)
-        .build();
+    if window_sessions.is_empty() {
+        return default_window_init(app.handle());
+    }
 
-    // 构建主窗口
-    let window = WebviewWindowBuilder::new(app, DEFAULT_WINDOW_LABEL, WebviewUrl::default())
-        .resizable(true)
-        .min_inner_size(460., 400.)
-        .inner_size(1000., 600.)
-        .center()
-        .resizable(true)
-        .fullscreen(false)
-        .decorations(true)
-        // title_bar_style 仅支持mac
-        .title_bar_style(TitleBarStyle::Overlay)
-        .hidden_title(true)
-        // TODO 不生效 https://github.com/tauri-apps/tauri/blob/08bda64c25008bd45c5b58d06ff14649081a2f5d/crates/tauri-runtime/src/lib.rs#L950
-        .traffic_light_position(LogicalPosition::new(15.0, 15.0))
-        // 透明模糊
-        .transparent(true)
-        .effects(effects)
-        .build()?;
+    for window_session in window_sessions.iter() {
+        create_browser_window(
+            app.handle(),
+            &window_session.label,
+            window_session.geometry,
+            window_session.sidebar_width,
+        )?;
+    }
+
+    let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+    let mut tm = tab_manager.lock().unwrap();
+    if let Some(snapshot) = snapshot.as_ref() {
+        tm.spaces_mut().restore(snapshot.spaces.clone());
+    }
+    for window_session in window_sessions.iter() {
+        let window_label = window_session.label.as_str();
+        tm.restore_folders(window_label, window_session.folders.clone());
+        // 恢复上次会话的 tab，只有激活的 tab 会立即创建 webview
+        let mut first_tab_id = None;
+        for tab in window_session.tabs.iter() {
+            let tab_id = tm.restore_tab(window_label, tab);
+            first_tab_id.get_or_insert(tab_id);
+        }
+        if let Some(active_id) = window_session
+            .active_tab_id
+            .filter(|id| window_session.tabs.iter().any(|t| t.id == *id))
+        {
+            first_tab_id = Some(active_id);
+        }
+        if let Some(tab_id) = first_tab_id {
+            if let Err(e) = tm.switch_tab(app.handle(), window_label, tab_id) {
+                eprintln!("[Setup:restore] Failed to activate tab {}: {}", tab_id, e);
+            }
+        }
+    }
 
-    sidebar_manager().set(DEFAULT_WINDOW_LABEL, SidebarState { width: 205.0 });
+    Ok(())
+}
+
+// 没有可恢复的会话时，创建主窗口并打开默认链接
+fn default_window_init(app: &AppHandle) -> tauri::Result<()> {
+    create_browser_window(app, DEFAULT_WINDOW_LABEL, None, DEFAULT_SIDEBAR_WIDTH)?;
+
+    let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+    let mut tm = tab_manager.lock().unwrap();
+    let mut first_tab_id = None;
 
     let links = [
         ("https://github.com/calebax/Rin", "Rin Browser"),
@@ -98,13 +149,9 @@ fn window_init(app: &App) -> tauri::Result<WebviewWindow> {
         ("https://www.google.com", "Google"),
     ];
 
-    let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
-    let mut tm = tab_manager.lock().unwrap();
-    let mut first_tab_id = None;
-
     for (i, (url, name)) in links.iter().enumerate() {
         let tab_id = tm
-            .create_tab(&app.handle(), DEFAULT_WINDOW_LABEL, url, name)
+            .create_tab(app, DEFAULT_WINDOW_LABEL, url, name, true)
             .unwrap();
 
         if i == 0 {
@@ -112,37 +159,195 @@ fn window_init(app: &App) -> tauri::Result<WebviewWindow> {
         }
     }
 
-    // 创建全局右下角浮层窗口
-    let _ = create_overlay_window(app, &window)?;
-
     if let Some(tab_id) = first_tab_id {
-        tm.switch_tab(&app.handle(), DEFAULT_WINDOW_LABEL, tab_id)
-            .unwrap();
+        tm.switch_tab(app, DEFAULT_WINDOW_LABEL, tab_id).unwrap();
     }
 
+    Ok(())
+}
+
+/// 新建浏览器窗口并打开一个 tab，private 为 true 时为隐私窗口，返回窗口 label
+pub fn open_new_window(app: &AppHandle, url: Option<&str>, private: bool) -> Result<String> {
+    // 窗口需在加锁前创建，窗口事件回调同样会获取 TabManager
+    let window_label = new_window_label();
+    create_browser_window(app, &window_label, None, DEFAULT_SIDEBAR_WIDTH)?;
+
+    let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+    let mut tm = tab_manager.lock().unwrap();
+    if private {
+        tm.set_window_private(&window_label, Uuid::new_v4());
+    } else {
+        // 最后一个普通窗口关闭时冻结了会话，新的普通窗口需要重新保存
+        session::unfreeze();
+    }
+    let tab_id = tm
+        .create_tab(
+            app,
+            &window_label,
+            url.unwrap_or(NEW_TAB_URL),
+            NEW_TAB_NAME,
+            false,
+        )
+        .map_err(anyhow::Error::msg)?;
+    tm.switch_tab(app, &window_label, tab_id)
+        .map_err(anyhow::Error::msg)?;
+
+    Ok(window_label)
+}
+
+/// 生成新浏览器窗口的 label
+pub fn new_window_label() -> String {
+    format!("window-{}", Uuid::new_v4().simple())
+}
+
+/// 构建浏览器窗口：视觉特效、侧栏状态、AI 浮层与窗口事件
+pub fn create_browser_window(
+    app: &AppHandle,
+    window_label: &str,
+    geometry: Option<WindowGeometry>,
+    sidebar_width: f64,
+) -> tauri::Result<WebviewWindow> {
+    // 构建窗口视觉特效
+    let effects = EffectsBuilder::new()
+        .effects(vec![Effect::Mica, Effect::Acrylic, Effect::HudWindow])
+        .radius(12.0)
+        .build();
+
+    let mut window_builder = WebviewWindowBuilder::new(app, window_label, WebviewUrl::default())
+        .resizable(true)
+        .min_inner_size(460., 400.);
+    // 上次的位置所在显示器已不存在时重新居中
+    window_builder = match geometry.and_then(|g| clamp_to_monitors(app, g)) {
+        Some(geometry) => window_builder
+            .position(geometry.x, geometry.y)
+            .inner_size(geometry.width, geometry.height),
+        None => window_builder.inner_size(1000., 600.).center(),
+    };
+    let window = window_builder
+        .resizable(true)
+        .maximized(geometry.is_some_and(|g| g.maximized))
+        .fullscreen(geometry.is_some_and(|g| g.fullscreen))
+        .decorations(true)
+        // title_bar_style 仅支持mac
+        .title_bar_style(TitleBarStyle::Overlay)
+        .hidden_title(true)
+        // TODO 不生效 https://github.com/tauri-apps/tauri/blob/08bda64c25008bd45c5b58d06ff14649081a2f5d/crates/tauri-runtime/src/lib.rs#L950
+        .traffic_light_position(LogicalPosition::new(15.0, 15.0))
+        // 透明模糊
+        .transparent(true)
+        .effects(effects)
+        .build()?;
+
+    sidebar_manager().set(
+        window_label,
+        SidebarState {
+            width: sidebar_width,
+        },
+    );
+
+    // 创建右下角浮层窗口，需先于窗口事件注册，resize 时会同步浮层位置
+    create_overlay_window(app, &window)?;
+    register_window_events(app, &window);
+
     Ok(window)
 }
 
+/// 把窗口限制在当前存在的显示器内：选择重叠面积最大的显示器，尺寸不超过该显示器
+fn clamp_to_monitors(app: &AppHandle, geometry: WindowGeometry) -> Option<WindowGeometry> {
+    let monitors = app.available_monitors().ok()?;
+    // 显示器区域（逻辑像素）
+    let areas = monitors.iter().map(|m| {
+        let scale_factor = m.scale_factor();
+        (
+            m.position().x as f64 / scale_factor,
+            m.position().y as f64 / scale_factor,
+            m.size().width as f64 / scale_factor,
+            m.size().height as f64 / scale_factor,
+        )
+    });
+    let overlap = |(x, y, width, height): (f64, f64, f64, f64)| {
+        let w = (geometry.x + geometry.width).min(x + width) - geometry.x.max(x);
+        let h = (geometry.y + geometry.height).min(y + height) - geometry.y.max(y);
+        (w.max(0.), h.max(0.))
+    };
+    let (x, y, width, height) = areas
+        .filter(|area| {
+            let (w, h) = overlap(*area);
+            w >= MIN_VISIBLE_SIZE && h >= MIN_VISIBLE_SIZE
+        })
+        .max_by(|a, b| {
+            let (aw, ah) = overlap(*a);
+            let (bw, bh) = overlap(*b);
+            (aw * ah).total_cmp(&(bw * bh))
+        })?;
+
+    let clamped_width = geometry.width.min(width);
+    let clamped_height = geometry.height.min(height);
+    Some(WindowGeometry {
+        x: geometry.x.clamp(x, x + width - clamped_width),
+        y: geometry.y.clamp(y, y + height - clamped_height),
+        width: clamped_width,
+        height: clamped_height,
+        ..geometry
+    })
+}
+
+fn register_window_events(app: &AppHandle, window: &WebviewWindow) {
+    let window_label = window.label().to_string();
+    let handle_clone = app.clone();
+
+    window.on_window_event(move |event| match event {
+        WindowEvent::Resized(_size) => {
+            let tab_manager = handle_clone.state::<Arc<Mutex<TabManager>>>();
+            let tm = tab_manager.lock().unwrap();
+            tm.tab_resized(&handle_clone, &window_label);
+            session::mark_dirty();
+        }
+        WindowEvent::Moved(_position) => session::mark_dirty(),
+        // 窗口已关闭，丢弃其 tab 与侧栏状态
+        WindowEvent::Destroyed => {
+            let tab_manager = handle_clone.state::<Arc<Mutex<TabManager>>>();
+            let (remaining, unused_store) =
+                tab_manager.lock().unwrap().remove_window(&window_label);
+            sidebar_manager().remove(&window_label);
+            // 隐私窗口的数据存储不再被使用时删除
+            if let Some(store_id) = unused_store {
+                private_data::remove(&handle_clone, store_id);
+            }
+            // 最后一个普通窗口关闭时保留会话，供下次启动恢复；隐私窗口不计入会话
+            if remaining == 0 {
+                session::freeze();
+            } else {
+                session::mark_dirty();
+            }
+        }
+        // 窗口销毁前保存，保留其几何信息
+        WindowEvent::CloseRequested { .. } => {
+            if let Err(e) = session::save_session(&handle_clone) {
+                eprintln!("[Session:save] Failed to save session: {:#}", e);
+            }
+        }
+        _ => {}
+    });
+}
+
 // 创建一个全局独立 WebView 浮层窗口（透明高斯模糊，右下角）
-fn create_overlay_window(app: &App, parent_window: &WebviewWindow) -> Result<()> {
+fn create_overlay_window(app: &AppHandle, parent_window: &WebviewWindow) -> Result<()> {
     let overlay_label = format!("{}-ai-overlay", parent_window.label());
     let effects = EffectsBuilder::new()
         // .effects(vec![Effect::Mica, Effect::Acrylic, Effect::HudWindow])
         .radius(18.0)
         .build();
-    let _overlay_window = tauri::WebviewWindowBuilder::new(
-        app.handle(),
-        overlay_label,
-        WebviewUrl::App("overlay".into()),
-    )
-    .parent(parent_window)?
-    .decorations(false)
-    .transparent(true)
-    .effects(effects)
-    .position(10., 10.)
-    .inner_size(10., 10.)
-    .accept_first_mouse(true)
-    .build()?;
+    let _overlay_window =
+        tauri::WebviewWindowBuilder::new(app, overlay_label, WebviewUrl::App("overlay".into()))
+            .parent(parent_window)?
+            .decorations(false)
+            .transparent(true)
+            .effects(effects)
+            .position(10., 10.)
+            .inner_size(10., 10.)
+            .accept_first_mouse(true)
+            .build()?;
 
     Ok(())
 }
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_SPACE_NAME: &str = "Space";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Space {
+    pub id: Uuid,
+    pub name: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub index: usize,
+}
+
+// 工作区管理，所有窗口共享同一组 space
+pub struct SpaceManager {
+    spaces: HashMap<Uuid, Space>,
+}
+
+impl SpaceManager {
+    pub fn new() -> Self {
+        let mut manager = Self {
+            spaces: HashMap::new(),
+        };
+        manager.create(DEFAULT_SPACE_NAME, None, None);
+        manager
+    }
+
+    /// 按 index 排序的 space 列表
+    pub fn list(&self) -> Vec<Space> {
+        let mut spaces: Vec<Space> = self.spaces.values().cloned().collect();
+        spaces.sort_by_key(|s| s.index);
+        spaces
+    }
+
+    pub fn contains(&self, space_id: &Uuid) -> bool {
+        self.spaces.contains_key(space_id)
+    }
+
+    /// 默认 space，即排序第一的 space
+    pub fn default_space_id(&self) -> Uuid {
+        self.spaces
+            .values()
+            .min_by_key(|s| s.index)
+            .map(|s| s.id)
+            .expect("SpaceManager always keeps at least one space")
+    }
+
+    pub fn create(&mut self, name: &str, icon: Option<String>, color: Option<String>) -> Space {
+        let space = Space {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            icon,
+            color,
+            index: self.spaces.len(),
+        };
+        self.spaces.insert(space.id, space.clone());
+        space
+    }
+
+    pub fn rename(&mut self, space_id: &Uuid, name: &str) -> Result<(), String> {
+        let space = self.spaces.get_mut(space_id).ok_or("Space not found")?;
+        space.name = name.to_string();
+        Ok(())
+    }
+
+    pub fn set_appearance(
+        &mut self,
+        space_id: &Uuid,
+        icon: Option<String>,
+        color: Option<String>,
+    ) -> Result<(), String> {
+        let space = self.spaces.get_mut(space_id).ok_or("Space not found")?;
+        space.icon = icon;
+        space.color = color;
+        Ok(())
+    }
+
+    /// 移动 space 到指定位置，返回新的排序
+    pub fn reorder(&mut self, space_id: &Uuid, new_index: usize) -> Result<Vec<Space>, String> {
+        let mut ids: Vec<Uuid> = self.list().iter().map(|s| s.id).collect();
+        let from = ids
+            .iter()
+            .position(|id| id == space_id)
+            .ok_or("Space not found")?;
+        let id = ids.remove(from);
+        ids.insert(new_index.min(ids.len()), id);
+        self.apply_order(&ids);
+        Ok(self.list())
+    }
+
+    /// 删除 space，至少保留一个
+    pub fn remove(&mut self, space_id: &Uuid) -> Result<Space, String> {
+        if self.spaces.len() <= 1 {
+            return Err("Cannot delete the last space".into());
+        }
+        let space = self.spaces.remove(space_id).ok_or("Space not found")?;
+        let ids: Vec<Uuid> = self.list().iter().map(|s| s.id).collect();
+        self.apply_order(&ids);
+        Ok(space)
+    }
+
+    /// 用会话中的 space 替换当前列表
+    pub fn restore(&mut self, spaces: Vec<Space>) {
+        if spaces.is_empty() {
+            return;
+        }
+        self.spaces = spaces.into_iter().map(|s| (s.id, s)).collect();
+        let ids: Vec<Uuid> = self.list().iter().map(|s| s.id).collect();
+        self.apply_order(&ids);
+    }
+
+    fn apply_order(&mut self, ids: &[Uuid]) {
+        for (index, id) in ids.iter().enumerate() {
+            if let Some(space) = self.spaces.get_mut(id) {
+                space.index = index;
+            }
+        }
+    }
+}
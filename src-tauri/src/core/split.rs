@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// 分屏最多同时显示的 tab 数量
+pub const MAX_SPLIT_PANES: usize = 4;
+// 单个窗格最小占比
+const MIN_PANE_RATIO: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SplitLayout {
+    // 左右并排
+    Horizontal,
+    // 上下堆叠
+    Vertical,
+    // 两列网格
+    Grid,
+}
+
+/// 窗格在内容区中的位置与尺寸，取值为 0..1 的占比
+#[derive(Debug, Clone, Copy)]
+pub struct PaneRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl PaneRect {
+    pub const FULL: PaneRect = PaneRect {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+}
+
+/// 分屏状态，供前端渲染分隔条
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitViewInfo {
+    pub layout: SplitLayout,
+    pub ratios: Vec<f64>,
+    pub tab_ids: Vec<Uuid>,
+}
+
+// 单个窗口的分屏布局
+// ratios 为分隔条位置：左右/上下布局为 n-1 个递增位置，网格为 [列分隔, 行分隔]
+pub struct SplitView {
+    layout: SplitLayout,
+    ratios: Vec<f64>,
+}
+
+impl Default for SplitView {
+    fn default() -> Self {
+        Self {
+            layout: SplitLayout::Horizontal,
+            ratios: Vec::new(),
+        }
+    }
+}
+
+impl SplitView {
+    pub fn layout(&self) -> SplitLayout {
+        self.layout
+    }
+
+    pub fn ratios(&self) -> Vec<f64> {
+        self.ratios.clone()
+    }
+
+    /// 窗格数量变化后恢复均分
+    pub fn reset(&mut self, pane_count: usize) {
+        self.ratios = match self.layout {
+            SplitLayout::Grid => vec![0.5, 0.5],
+            _ => (1..pane_count.max(1))
+                .map(|i| i as f64 / pane_count as f64)
+                .collect(),
+        };
+    }
+
+    pub fn set_layout(&mut self, layout: SplitLayout, pane_count: usize) {
+        self.layout = layout;
+        self.reset(pane_count);
+    }
+
+    /// 调整分隔条位置，超出范围的值会被收紧
+    pub fn set_ratios(&mut self, ratios: Vec<f64>, pane_count: usize) -> Result<(), String> {
+        let expected = match self.layout {
+            SplitLayout::Grid => 2,
+            _ => pane_count.saturating_sub(1),
+        };
+        if ratios.len() != expected {
+            return Err(format!("Expected {} split ratios", expected));
+        }
+        let ratios: Vec<f64> = ratios
+            .into_iter()
+            .map(|r| r.clamp(MIN_PANE_RATIO, 1.0 - MIN_PANE_RATIO))
+            .collect();
+        if self.layout != SplitLayout::Grid
+            && ratios.windows(2).any(|w| w[1] - w[0] < MIN_PANE_RATIO)
+        {
+            return Err("Split panes are too small".into());
+        }
+        self.ratios = ratios;
+        Ok(())
+    }
+
+    /// 按布局计算每个窗格的位置，顺序与窗格顺序一致
+    pub fn pane_rects(&self, pane_count: usize) -> Vec<PaneRect> {
+        if pane_count <= 1 {
+            return vec![PaneRect::FULL];
+        }
+        match self.layout {
+            SplitLayout::Horizontal => self
+                .edges(pane_count)
+                .windows(2)
+                .map(|e| PaneRect {
+                    x: e[0],
+                    width: e[1] - e[0],
+                    ..PaneRect::FULL
+                })
+                .collect(),
+            SplitLayout::Vertical => self
+                .edges(pane_count)
+                .windows(2)
+                .map(|e| PaneRect {
+                    y: e[0],
+                    height: e[1] - e[0],
+                    ..PaneRect::FULL
+                })
+                .collect(),
+            SplitLayout::Grid => {
+                let col = self.ratios.first().copied().unwrap_or(0.5);
+                let row = self.ratios.get(1).copied().unwrap_or(0.5);
+                let rows = pane_count.div_ceil(2);
+                (0..pane_count)
+                    .map(|i| {
+                        let (r, c) = (i / 2, i % 2);
+                        let (y, height) = match (rows, r) {
+                            (1, _) => (0.0, 1.0),
+                            (_, 0) => (0.0, row),
+                            _ => (row, 1.0 - row),
+                        };
+                        // 奇数个窗格时最后一个占满整行
+                        let (x, width) = if i == pane_count - 1 && c == 0 {
+                            (0.0, 1.0)
+                        } else if c == 0 {
+                            (0.0, col)
+                        } else {
+                            (col, 1.0 - col)
+                        };
+                        PaneRect {
+                            x,
+                            y,
+                            width,
+                            height,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn edges(&self, pane_count: usize) -> Vec<f64> {
+        let mut edges = vec![0.0];
+        if self.ratios.len() + 1 == pane_count {
+            edges.extend(self.ratios.iter().copied());
+        } else {
+            edges.extend((1..pane_count).map(|i| i as f64 / pane_count as f64));
+        }
+        edges.push(1.0);
+        edges
+    }
+}
@@ -1,14 +1,28 @@
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, LogicalPosition, LogicalSize, Manager, Webview, Window};
 use uuid::Uuid;
 
 use crate::core::ai_overlay::overlay_resized;
+use crate::core::bridge::report_scroll_script;
+use crate::core::closed_tab::{ClosedTab, ClosedTabs};
+#[cfg(target_os = "macos")]
+use crate::core::crash;
+use crate::core::folder::{Folder, TabFolders};
+use crate::core::history::{TabHistory, TabHistoryInfo, Traversal};
 use crate::core::layout::{
     get_sidebar_width, get_window_scale_factor, set_webview_corner_radius, set_webview_properties,
 };
+use crate::core::popup::{self, BlockedPopup};
+use crate::core::session;
+use crate::core::settings::{self, CloseTabActivation};
+use crate::core::space::SpaceManager;
+use crate::core::split::{PaneRect, SplitLayout, SplitView, SplitViewInfo, MAX_SPLIT_PANES};
+use crate::core::tab_event::{emit_tab_event, emit_tab_list_event, TabEventKind, TabListEventKind};
 use crate::core::webview::create_webview_builder;
+use crate::core::zoom::{self, ZoomAction};
 
 const TAB_MARGIN: f64 = 10.0;
 
@@ -18,6 +32,29 @@ pub enum TabNavigation {
     Reload,
     Forward,
     NavigateTo(String), // 可选，用于跳转到指定 URL
+    GoToEntry(usize),   // 跳转到导航历史中的指定记录
+}
+
+/// 批量关闭的范围，固定 tab 不会被批量关闭
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum CloseTabsRule {
+    // 指定的 tab
+    Selection { tab_ids: Vec<Uuid> },
+    // 当前 space 中除 tab_id 以外的 tab
+    Others { tab_id: Uuid },
+    // 与 tab_id 同级且位于其后的 tab
+    ToRight { tab_id: Uuid },
+    // 与 tab_id 同级且位于其前的 tab
+    ToLeft { tab_id: Uuid },
+    // 文件夹及其子文件夹中的 tab，文件夹保留
+    Folder { folder_id: Uuid },
+    // 当前 space 中地址相同的 tab，保留活跃或排在最前的一个
+    Duplicates,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,138 +70,1467 @@ pub struct Tab {
 
     pub is_pinned: bool,
     pub is_active: bool,
+    // 固定 tab 的主页地址，可一键回到该地址
+    #[serde(default)]
+    pub pinned_url: Option<String>,
+    // 没有 webview（已休眠或延迟创建），下次切换时创建
+    #[serde(default)]
+    pub is_hibernated: bool,
+    // 最近一次处于活跃状态的时间（毫秒时间戳）
+    #[serde(default)]
+    pub last_active_at: u64,
+    // 页面缩放比例
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
 }
 
-// 状态管理
-pub struct TabManager {
+fn default_zoom() -> f64 {
+    zoom::DEFAULT_ZOOM
+}
+
+/// 侧边栏渲染所需的 tab 与文件夹
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabInfoList {
+    pub tabs: Vec<Tab>,
+    pub folders: Vec<Folder>,
+}
+
+// 单个窗口的 tab 状态，每个窗口独立维护 tab 集合、活跃 tab 与排序
+struct WindowTabs {
     tabs: HashMap<Uuid, Tab>,
     active_tab_ids: Vec<Uuid>,
+    // 当前显示的 space
+    space_id: Uuid,
+    // 每个 space 最近一次活跃的 tab
+    space_active_tab: HashMap<Uuid, Uuid>,
+    folders: TabFolders,
+    closed_tabs: ClosedTabs,
+    // 多个活跃 tab 时的分屏布局，顺序与 active_tab_ids 一致
+    split: SplitView,
+    // 每个 tab 的导航历史
+    histories: HashMap<Uuid, TabHistory>,
+    // 最近使用顺序，最近的在最前
+    mru: Vec<Uuid>,
+    // 复制 tab 时待恢复的滚动位置：(页面地址, x, y)
+    pending_scroll: HashMap<Uuid, (String, f64, f64)>,
+    // 当前页面被拦截的弹窗
+    blocked_popups: HashMap<Uuid, Vec<BlockedPopup>>,
+    // 隐私窗口的数据存储，窗口内的 tab 共用，关闭后删除；不保存会话、不写入磁盘缓存
+    private_store: Option<Uuid>,
 }
 
-impl TabManager {
-    pub fn new() -> Self {
+impl WindowTabs {
+    fn new(space_id: Uuid) -> Self {
         Self {
             tabs: HashMap::new(),
             active_tab_ids: Vec::new(),
+            space_id,
+            space_active_tab: HashMap::new(),
+            folders: TabFolders::default(),
+            closed_tabs: ClosedTabs::default(),
+            split: SplitView::default(),
+            histories: HashMap::new(),
+            mru: Vec::new(),
+            pending_scroll: HashMap::new(),
+            blocked_popups: HashMap::new(),
+            private_store: None,
+        }
+    }
+
+    /// 按 index 重新压缩排序，固定区与普通区、各文件夹内单独计数
+    fn reindex(&mut self) {
+        let mut tab_list: Vec<_> = self.tabs.values_mut().collect();
+        tab_list.sort_by_key(|t| (t.space_id, !t.is_pinned, t.folder_id, t.index));
+        let mut group = None;
+        let mut next_index = 0;
+        for tab in tab_list {
+            let key = (tab.space_id, tab.is_pinned, tab.folder_id);
+            if group != Some(key) {
+                group = Some(key);
+                next_index = 0;
+            }
+            tab.index = next_index;
+            next_index += 1;
+        }
+    }
+
+    /// 同级 tab 数量
+    fn group_len(&self, space_id: &Uuid, is_pinned: bool, folder_id: Option<Uuid>) -> usize {
+        self.tabs
+            .values()
+            .filter(|t| {
+                t.space_id == *space_id && t.is_pinned == is_pinned && t.folder_id == folder_id
+            })
+            .count()
+    }
+
+    /// space 中排在最前的 tab，顶层 tab 优先
+    fn first_tab_in_space(&self, space_id: &Uuid) -> Option<Uuid> {
+        self.tabs
+            .values()
+            .filter(|t| t.space_id == *space_id)
+            .min_by_key(|t| (!t.is_pinned, t.folder_id.is_some(), t.index))
+            .map(|t| t.id)
+    }
+
+    /// 记录 tab 为最近使用
+    fn touch_mru(&mut self, tab_id: Uuid) {
+        self.mru.retain(|id| *id != tab_id);
+        self.mru.insert(0, tab_id);
+    }
+
+    /// 活跃 tab 关闭后接替它的 tab，只在同一 space 中查找
+    fn successor(&self, closed: &Tab, activation: CloseTabActivation) -> Option<Uuid> {
+        let mru = || {
+            self.mru.iter().copied().find(|id| {
+                *id != closed.id
+                    && self
+                        .tabs
+                        .get(id)
+                        .is_some_and(|t| t.space_id == closed.space_id)
+            })
+        };
+        // 同级中后一个 tab，没有则取前一个
+        let neighbor = || {
+            let siblings: Vec<&Tab> = self
+                .tabs
+                .values()
+                .filter(|t| {
+                    t.id != closed.id
+                        && t.space_id == closed.space_id
+                        && t.is_pinned == closed.is_pinned
+                        && t.folder_id == closed.folder_id
+                })
+                .collect();
+            siblings
+                .iter()
+                .filter(|t| t.index >= closed.index)
+                .min_by_key(|t| t.index)
+                .or_else(|| siblings.iter().max_by_key(|t| t.index))
+                .map(|t| t.id)
+        };
+        let preferred = match activation {
+            CloseTabActivation::Mru => mru().or_else(neighbor),
+            CloseTabActivation::Neighbor => neighbor().or_else(mru),
+        };
+        preferred.or_else(|| {
+            self.first_tab_in_space(&closed.space_id)
+                .filter(|id| *id != closed.id)
+        })
+    }
+
+    /// 已卸载或休眠的 tab 重新创建 webview
+    fn ensure_webview(
+        &mut self,
+        app: &AppHandle,
+        window: &Window,
+        tab_id: &Uuid,
+    ) -> Result<Webview, String> {
+        if let Some(webview) = window.get_webview(&tab_id.to_string()) {
+            return Ok(webview);
+        }
+        let url = self.tabs.get(tab_id).ok_or("Tab not found")?.url.clone();
+        attach_webview(app, window.label(), tab_id, &url, self.private_store)?;
+        if let Some(history) = self.histories.get_mut(tab_id) {
+            history.webview_recreated();
+        }
+        window
+            .get_webview(&tab_id.to_string())
+            .ok_or_else(|| "WebView not found".into())
+    }
+
+    /// 隐藏当前活跃的 tab
+    fn hide_active(&mut self, window: &Window) {
+        let now = now_millis();
+        for old_id in self.active_tab_ids.drain(..) {
+            if let Some(tab) = self.tabs.get_mut(&old_id) {
+                tab.is_active = false;
+                tab.last_active_at = now;
+
+                if let Some(webview) = window.get_webview(&old_id.to_string()) {
+                    let _ = webview.hide(); // 忽略错误
+                }
+            }
+        }
+    }
+}
+
+// 状态管理，按 window_label 划分
+pub struct TabManager {
+    windows: HashMap<String, WindowTabs>,
+    spaces: SpaceManager,
+}
+
+impl TabManager {
+    pub fn new() -> Self {
+        Self {
+            windows: HashMap::new(),
+            spaces: SpaceManager::new(),
+        }
+    }
+
+    pub fn spaces(&self) -> &SpaceManager {
+        &self.spaces
+    }
+
+    pub fn spaces_mut(&mut self) -> &mut SpaceManager {
+        &mut self.spaces
+    }
+
+    /// 根据window_label获取当前 space 的tab列表与文件夹树
+    pub fn get_tab_info_list(&self, window_label: &str) -> TabInfoList {
+        let Some(win_tabs) = self.windows.get(window_label) else {
+            return TabInfoList {
+                tabs: Vec::new(),
+                folders: Vec::new(),
+            };
+        };
+        let mut tabs: Vec<Tab> = win_tabs
+            .tabs
+            .values()
+            .filter(|t| t.space_id == win_tabs.space_id)
+            .cloned()
+            .collect();
+        tabs.sort_by_key(|t| (!t.is_pinned, t.folder_id, t.index));
+
+        TabInfoList {
+            tabs,
+            folders: win_tabs.folders.list(&win_tabs.space_id),
         }
     }
 
-    /// 根据window_label获取当前tab列表
-    pub fn get_tab_info_list(&self, _window_label: String) -> Vec<Tab> {
-        self.tabs.values().cloned().collect()
+    /// 窗口内所有 space 的 tab，用于会话保存
+    pub fn get_all_tabs(&self, window_label: &str) -> Vec<Tab> {
+        let mut tabs: Vec<Tab> = self
+            .windows
+            .get(window_label)
+            .map(|w| w.tabs.values().cloned().collect())
+            .unwrap_or_default();
+        tabs.sort_by_key(|t| (t.space_id, !t.is_pinned, t.folder_id, t.index));
+        tabs
+    }
+
+    /// 窗口内所有 space 的文件夹，用于会话保存
+    pub fn get_all_folders(&self, window_label: &str) -> Vec<Folder> {
+        self.windows
+            .get(window_label)
+            .map(|w| w.folders.all())
+            .unwrap_or_default()
     }
 
+    /// 窗口当前显示的 space
+    pub fn get_window_space_id(&self, window_label: &str) -> Uuid {
+        self.windows
+            .get(window_label)
+            .map(|w| w.space_id)
+            .unwrap_or_else(|| self.spaces.default_space_id())
+    }
+
+    /// 当前有 tab 的窗口列表
+    pub fn window_labels(&self) -> Vec<String> {
+        self.windows.keys().cloned().collect()
+    }
+
+    /// 窗口当前活跃的 tab
+    pub fn get_active_tab_id(&self, window_label: &str) -> Option<Uuid> {
+        self.windows
+            .get(window_label)
+            .and_then(|w| w.active_tab_ids.first().copied())
+    }
+
+    /// 创建 tab，deferred 时只登记 tab，首次切换时才创建 webview
     pub fn create_tab(
         &mut self,
         app: &AppHandle,
         window_label: &str,
         search_query: &str,
         name: &str,
+        deferred: bool,
     ) -> Result<Uuid, String> {
-        // 获取宿主 Window
-        let window: Window = app.get_window(window_label).ok_or("Window not found")?;
         // 生成 TabId
         let tab_id = self.gen_id();
-        // 窗口尺寸
-        let position = LogicalPosition::new(get_sidebar_width(window_label), 50.);
-        let size = LogicalSize::new(0., 0.);
-
-        let webview_builder = create_webview_builder(app, &tab_id, search_query);
-        let _ = window
-            .add_child(webview_builder, position, size)
-            .map_err(|e| e.to_string())
-            .inspect(|wv| {
-                let _ = wv.with_webview(|webview| {
-                    #[cfg(target_os = "macos")]
-                    unsafe {
-                        set_webview_corner_radius(webview.inner(), 12.0);
-                    }
-                });
-
-                let _ = wv.hide();
-            });
+        if !deferred {
+            let private_store = self.private_store(window_label);
+            attach_webview(app, window_label, &tab_id, search_query, private_store)?;
+        }
+
         // 准备 Tab 数据
+        let win_tabs = self.window_tabs_mut(window_label);
+        let space_id = win_tabs.space_id;
         let new_tab = Tab {
             id: tab_id,
-            space_id: tab_id, // TODO: 这里可能要改
+            space_id,
             folder_id: None,
             name: name.to_string(),
             url: search_query.to_string(),
             favicon: None,
-            index: self.tabs.len(),
+            index: win_tabs.group_len(&space_id, false, None),
             is_pinned: false,
             is_active: false,
+            pinned_url: None,
+            is_hibernated: deferred,
+            last_active_at: now_millis(),
+            zoom: zoom::site_origin(search_query)
+                .map_or(zoom::DEFAULT_ZOOM, |o| zoom::site_zoom(&o)),
         };
-        self.tabs.insert(tab_id, new_tab);
+        win_tabs.tabs.insert(tab_id, new_tab);
         println!("Created tab with id: {}", tab_id);
+        session::mark_dirty();
 
         Ok(tab_id)
     }
 
+    /// 复制 tab 到其后方并激活，保留导航历史，源 tab 已加载时同步滚动位置
+    pub fn duplicate_tab(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> Result<Uuid, String> {
+        let win_tabs = self.windows.get(window_label).ok_or("Window not found")?;
+        let source = win_tabs.tabs.get(&tab_id).ok_or("Tab not found")?.clone();
+        let history = win_tabs.histories.get(&tab_id).cloned();
+
+        let new_id = self.gen_id();
+        let win_tabs = self.window_tabs_mut(window_label);
+        win_tabs.tabs.insert(
+            new_id,
+            Tab {
+                id: new_id,
+                index: usize::MAX,
+                is_active: false,
+                // webview 在激活时创建
+                is_hibernated: true,
+                last_active_at: now_millis(),
+                ..source.clone()
+            },
+        );
+        if let Some(history) = history {
+            win_tabs.histories.insert(new_id, history);
+        }
+        win_tabs.reindex();
+        self.move_tab(window_label, new_id, source.index + 1, source.folder_id)?;
+        self.switch_tab(app, window_label, new_id)?;
+
+        // 让源页面上报滚动位置，新页面加载完成后恢复
+        if let Some(webview) = app
+            .get_window(window_label)
+            .and_then(|w| w.get_webview(&tab_id.to_string()))
+        {
+            if let Err(e) = webview.eval(report_scroll_script(&new_id)) {
+                eprintln!("[Tab:duplicate] Failed to read scroll position: {}", e);
+            }
+        }
+
+        Ok(new_id)
+    }
+
+    /// 页面打开的链接：在打开者后方新建 tab 并激活
+    pub fn open_tab_from_opener(
+        &mut self,
+        app: &AppHandle,
+        opener_tab_id: Uuid,
+        url: &str,
+    ) -> Result<Uuid, String> {
+        let window_label = self
+            .tab_window_label(&opener_tab_id)
+            .ok_or("Tab not found")?;
+        let opener = self.windows[&window_label].tabs[&opener_tab_id].clone();
+
+        let tab_id = self.create_tab(app, &window_label, url, url, false)?;
+        if let Some(tab) = self
+            .windows
+            .get_mut(&window_label)
+            .and_then(|w| w.tabs.get_mut(&tab_id))
+        {
+            tab.space_id = opener.space_id;
+        }
+        // 固定 tab 打开的链接排在普通 tab 最前
+        let (index, folder_id) = if opener.is_pinned {
+            (0, None)
+        } else {
+            (opener.index + 1, opener.folder_id)
+        };
+        self.move_tab(&window_label, tab_id, index, folder_id)?;
+        self.switch_tab(app, &window_label, tab_id)
+    }
+
+    /// 从会话快照恢复 tab，保留原有 id 与元数据，webview 延迟到首次切换时创建
+    pub fn restore_tab(&mut self, window_label: &str, tab: &Tab) -> Uuid {
+        // space 已不存在时归入默认 space
+        let space_id = if self.spaces.contains(&tab.space_id) {
+            tab.space_id
+        } else {
+            self.spaces.default_space_id()
+        };
+        let win_tabs = self.window_tabs_mut(window_label);
+        let folder_id = tab.folder_id.filter(|id| {
+            win_tabs
+                .folders
+                .get(id)
+                .is_some_and(|f| f.space_id == space_id)
+        });
+        win_tabs.tabs.insert(
+            tab.id,
+            Tab {
+                space_id,
+                folder_id,
+                is_active: false,
+                is_hibernated: true,
+                last_active_at: now_millis(),
+                ..tab.clone()
+            },
+        );
+        win_tabs.reindex();
+
+        tab.id
+    }
+
     pub fn switch_tab(
         &mut self,
         app: &AppHandle,
         window_label: &str,
         tab_id: Uuid,
     ) -> Result<Uuid, String> {
-        // 如果目标 tab 已经活跃，直接返回
-        if self.active_tab_ids.contains(&tab_id) {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        // 只允许切换到本窗口的 tab
+        if !win_tabs.tabs.contains_key(&tab_id) {
+            return Err("Tab not found".into());
+        }
+        // 如果目标 tab 已经活跃（包括分屏中的窗格），只聚焦
+        if win_tabs.active_tab_ids.contains(&tab_id) {
+            if let Some(webview) = app
+                .get_window(window_label)
+                .and_then(|w| w.get_webview(&tab_id.to_string()))
+            {
+                let _ = webview.set_focus();
+            }
             return Ok(tab_id);
         }
 
         let window = app.get_window(window_label).ok_or("Window not found")?;
 
         // 更新目标 tab 状态
-        if let Some(tab) = self.tabs.get_mut(&tab_id) {
+        let webview = win_tabs.ensure_webview(app, &window, &tab_id)?;
+        let _ = webview.show();
+        let _ = webview.set_focus();
+        // 隐藏之前活跃的 tab，切换到分屏外的 tab 会退出分屏
+        win_tabs.hide_active(&window);
+
+        // 设置新的 active tab，窗口随之切换到 tab 所在的 space
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
             tab.is_active = true;
+            tab.is_hibernated = false;
+            tab.last_active_at = now_millis();
+            win_tabs.space_id = tab.space_id;
+            win_tabs.space_active_tab.insert(tab.space_id, tab_id);
+        }
+        win_tabs.active_tab_ids.push(tab_id);
+        win_tabs.touch_mru(tab_id);
+        session::mark_dirty();
 
-            if let Some(webview) = window.get_webview(&tab_id.to_string()) {
-                let _ = webview.show();
-                let _ = webview.set_focus();
+        self.tab_resized(app, window_label);
+
+        Ok(tab_id)
+    }
+
+    /// 从会话快照恢复窗口的文件夹，需在恢复 tab 之前调用
+    pub fn restore_folders(&mut self, window_label: &str, folders: Vec<Folder>) {
+        let folders = folders
+            .into_iter()
+            .filter(|f| self.spaces.contains(&f.space_id))
+            .collect();
+        self.window_tabs_mut(window_label).folders.restore(folders);
+    }
+
+    /// 关闭 tab，关闭的是活跃 tab 时按设置激活接替的 tab，返回关闭后的活跃 tab
+    pub fn close_tab(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> Result<Option<Uuid>, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        // 只允许关闭本窗口的 tab
+        let tab = win_tabs.tabs.get_mut(&tab_id).ok_or("Tab not found")?;
+
+        // 固定 tab 只卸载 webview，保留条目
+        if tab.is_pinned {
+            tab.is_active = false;
+            tab.is_hibernated = true;
+            let pane_count = win_tabs.active_tab_ids.len();
+            win_tabs.active_tab_ids.retain(|id| *id != tab_id);
+            // 分屏中的窗格被卸载时，剩余窗格重新布局
+            let split_changed = pane_count > 1 && win_tabs.active_tab_ids.len() != pane_count;
+            if split_changed {
+                win_tabs.split.reset(win_tabs.active_tab_ids.len());
+            }
+            if let Some(webview) = app
+                .get_window(window_label)
+                .and_then(|w| w.get_webview(&tab_id.to_string()))
+            {
+                let _ = webview.close();
+            }
+            session::mark_dirty();
+            let unloaded = tab.clone();
+            self.activate_successor(app, window_label, &unloaded);
+            if split_changed {
+                self.tab_resized(app, window_label);
+            }
+            return Ok(self.get_active_tab_id(window_label));
+        }
+        self.close_tabs(app, window_label, &[tab_id])?;
+        emit_tab_event(app, window_label, tab_id, TabEventKind::Closed);
+
+        Ok(self.get_active_tab_id(window_label))
+    }
+
+    /// 把窗口标记为使用 store_id 数据存储的隐私窗口，需在创建 tab 之前调用
+    pub fn set_window_private(&mut self, window_label: &str, store_id: Uuid) {
+        self.window_tabs_mut(window_label).private_store = Some(store_id);
+    }
+
+    /// 隐私窗口的数据存储，普通窗口为 None
+    pub fn private_store(&self, window_label: &str) -> Option<Uuid> {
+        self.windows.get(window_label).and_then(|w| w.private_store)
+    }
+
+    pub fn is_private_window(&self, window_label: &str) -> bool {
+        self.private_store(window_label).is_some()
+    }
+
+    /// tab 是否位于隐私窗口
+    pub fn is_private_tab(&self, tab_id: &Uuid) -> bool {
+        self.windows
+            .values()
+            .any(|w| w.private_store.is_some() && w.tabs.contains_key(tab_id))
+    }
+
+    /// 窗口销毁后丢弃其 tab 状态，webview 已随窗口销毁。
+    /// 返回剩余的非隐私窗口数，以及已无窗口使用、可以删除的隐私数据存储
+    pub fn remove_window(&mut self, window_label: &str) -> (usize, Option<Uuid>) {
+        let private_store = self
+            .windows
+            .remove(window_label)
+            .and_then(|w| w.private_store);
+        let unused_store = private_store.filter(|store| {
+            !self
+                .windows
+                .values()
+                .any(|w| w.private_store == Some(*store))
+        });
+        let remaining = self
+            .windows
+            .values()
+            .filter(|w| w.private_store.is_none())
+            .count();
+        (remaining, unused_store)
+    }
+
+    /// 窗口没有活跃 tab 时激活 closed 的接替者
+    fn activate_successor(&mut self, app: &AppHandle, window_label: &str, closed: &Tab) {
+        let Some(win_tabs) = self.windows.get(window_label) else {
+            return;
+        };
+        if !win_tabs.active_tab_ids.is_empty() {
+            return;
+        }
+        let activation = settings::settings().close_tab_activation;
+        if let Some(next_id) = win_tabs.successor(closed, activation) {
+            if let Err(e) = self.switch_tab(app, window_label, next_id) {
+                eprintln!("[Tab:close] Failed to activate tab {}: {}", next_id, e);
+            }
+        }
+    }
+
+    /// Ctrl+Tab 切换器使用的列表：当前 space 的 tab，按最近使用排序，未使用过的按位置排在后面
+    pub fn get_mru_tabs(&self, window_label: &str) -> Vec<Tab> {
+        let Some(win_tabs) = self.windows.get(window_label) else {
+            return Vec::new();
+        };
+        let mut tabs: Vec<Tab> = win_tabs
+            .tabs
+            .values()
+            .filter(|t| t.space_id == win_tabs.space_id)
+            .cloned()
+            .collect();
+        tabs.sort_by_key(|t| {
+            (
+                win_tabs
+                    .mru
+                    .iter()
+                    .position(|id| *id == t.id)
+                    .unwrap_or(usize::MAX),
+                !t.is_pinned,
+                t.folder_id.is_some(),
+                t.index,
+            )
+        });
+        tabs
+    }
+
+    /// 把 tab 连同其 webview 移动到另一个窗口，页面状态保持不变
+    pub fn move_tab_to_window(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+        target_window_label: &str,
+        index: Option<usize>,
+    ) -> Result<Uuid, String> {
+        if window_label == target_window_label {
+            return Err("Tab is already in the target window".into());
+        }
+        // webview 的数据存储创建后无法更换，只能在使用同一数据存储的窗口之间移动
+        if self.private_store(window_label) != self.private_store(target_window_label) {
+            return Err("Cannot move tabs between windows with different data stores".into());
+        }
+        let target_window = app
+            .get_window(target_window_label)
+            .ok_or("Target window not found")?;
+
+        if !self
+            .windows
+            .get(window_label)
+            .ok_or("Window not found")?
+            .tabs
+            .contains_key(&tab_id)
+        {
+            return Err("Tab not found".into());
+        }
+
+        // 先重新挂载到目标窗口，失败时 tab 仍留在源窗口；未加载的 tab 没有 webview 可挂载
+        if let Some(webview) = app
+            .get_window(window_label)
+            .and_then(|w| w.get_webview(&tab_id.to_string()))
+        {
+            webview
+                .reparent(&target_window)
+                .map_err(|e| format!("Failed to move tab {}: {}", tab_id, e))?;
+        }
+
+        // 从源窗口摘除 tab，webview 已移到目标窗口
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let mut tab = win_tabs.tabs.remove(&tab_id).ok_or("Tab not found")?;
+        let moved = tab.clone();
+        let history = win_tabs.histories.remove(&tab_id);
+        let was_active = win_tabs.active_tab_ids.contains(&tab_id);
+        win_tabs.active_tab_ids.retain(|id| *id != tab_id);
+        win_tabs.space_active_tab.retain(|_, id| *id != tab_id);
+        win_tabs.mru.retain(|id| *id != tab_id);
+        win_tabs.reindex();
+
+        // 归入目标窗口当前的 space，文件夹只在原窗口有效
+        let target_tabs = self.window_tabs_mut(target_window_label);
+        tab.space_id = target_tabs.space_id;
+        tab.folder_id = None;
+        tab.is_active = false;
+        tab.index = usize::MAX;
+        target_tabs.tabs.insert(tab_id, tab);
+        if let Some(history) = history {
+            target_tabs.histories.insert(tab_id, history);
+        }
+        target_tabs.reindex();
+        session::mark_dirty();
+
+        // 源窗口失去活跃 tab 时激活接替的 tab
+        if was_active {
+            self.activate_successor(app, window_label, &moved);
+        }
+
+        if let Some(index) = index {
+            self.move_tab(target_window_label, tab_id, index, None)?;
+        }
+        // 激活并按目标窗口尺寸重新布局
+        self.switch_tab(app, target_window_label, tab_id)
+    }
+
+    /// 固定 tab，记录当前地址为主页，排到固定区末尾
+    pub fn pin_tab(&mut self, window_label: &str, tab_id: Uuid) -> Result<(), String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let space_id = win_tabs
+            .tabs
+            .get(&tab_id)
+            .map(|t| t.space_id)
+            .ok_or("Tab not found")?;
+        let index = win_tabs.group_len(&space_id, true, None);
+
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            if tab.is_pinned {
+                return Ok(());
+            }
+            tab.is_pinned = true;
+            tab.folder_id = None;
+            tab.index = index;
+            tab.pinned_url = Some(tab.url.clone());
+        }
+        win_tabs.reindex();
+        session::mark_dirty();
+
+        Ok(())
+    }
+
+    /// 取消固定，tab 回到普通区最前
+    pub fn unpin_tab(&mut self, window_label: &str, tab_id: Uuid) -> Result<(), String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let tab = win_tabs.tabs.get(&tab_id).ok_or("Tab not found")?;
+        if !tab.is_pinned {
+            return Ok(());
+        }
+        let space_id = tab.space_id;
+
+        for other in win_tabs.tabs.values_mut() {
+            if other.space_id == space_id && !other.is_pinned && other.folder_id.is_none() {
+                other.index += 1;
+            }
+        }
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            tab.is_pinned = false;
+            tab.index = 0;
+            tab.pinned_url = None;
+        }
+        win_tabs.reindex();
+        session::mark_dirty();
+
+        Ok(())
+    }
+
+    /// 固定 tab 回到其主页地址
+    pub fn reset_pinned_tab(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> anyhow::Result<()> {
+        let pinned_url = self
+            .windows
+            .get(window_label)
+            .and_then(|w| w.tabs.get(&tab_id))
+            .context("Tab not found")?
+            .pinned_url
+            .clone()
+            .context("Tab is not pinned")?;
+
+        let loaded = app
+            .get_window(window_label)
+            .and_then(|w| w.get_webview(&tab_id.to_string()))
+            .is_some();
+        if loaded {
+            self.navigate(
+                app,
+                window_label,
+                tab_id,
+                TabNavigation::NavigateTo(pinned_url.clone()),
+            )?;
+        }
+        // 未加载时下次激活直接打开主页
+        if let Some(tab) = self
+            .windows
+            .get_mut(window_label)
+            .and_then(|w| w.tabs.get_mut(&tab_id))
+        {
+            tab.url = pinned_url;
+        }
+        session::mark_dirty();
+
+        Ok(())
+    }
+
+    /// 按规则批量关闭本窗口的 tab，作为一次操作记录，返回被关闭的 tab id
+    pub fn close_tabs_by_rule(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        rule: CloseTabsRule,
+    ) -> Result<Vec<Uuid>, String> {
+        let win_tabs = self.windows.get(window_label).ok_or("Window not found")?;
+        let space_id = win_tabs.space_id;
+        let closable = |t: &&Tab| !t.is_pinned;
+
+        let tab_ids: Vec<Uuid> = match &rule {
+            CloseTabsRule::Selection { tab_ids } => tab_ids
+                .iter()
+                .filter_map(|id| win_tabs.tabs.get(id))
+                .filter(closable)
+                .map(|t| t.id)
+                .collect(),
+            CloseTabsRule::Others { tab_id } => {
+                let anchor = win_tabs.tabs.get(tab_id).ok_or("Tab not found")?;
+                win_tabs
+                    .tabs
+                    .values()
+                    .filter(closable)
+                    .filter(|t| t.space_id == anchor.space_id && t.id != *tab_id)
+                    .map(|t| t.id)
+                    .collect()
+            }
+            CloseTabsRule::ToRight { tab_id } | CloseTabsRule::ToLeft { tab_id } => {
+                let anchor = win_tabs.tabs.get(tab_id).ok_or("Tab not found")?;
+                let to_right = matches!(rule, CloseTabsRule::ToRight { .. });
+                win_tabs
+                    .tabs
+                    .values()
+                    .filter(closable)
+                    .filter(|t| {
+                        t.space_id == anchor.space_id
+                            && t.is_pinned == anchor.is_pinned
+                            && t.folder_id == anchor.folder_id
+                    })
+                    .filter(|t| {
+                        if to_right {
+                            t.index > anchor.index
+                        } else {
+                            t.index < anchor.index
+                        }
+                    })
+                    .map(|t| t.id)
+                    .collect()
+            }
+            CloseTabsRule::Folder { folder_id } => {
+                if !win_tabs.folders.contains(folder_id) {
+                    return Err("Folder not found".into());
+                }
+                let folder_ids = win_tabs.folders.subtree(folder_id);
+                win_tabs
+                    .tabs
+                    .values()
+                    .filter(closable)
+                    .filter(|t| t.folder_id.is_some_and(|id| folder_ids.contains(&id)))
+                    .map(|t| t.id)
+                    .collect()
+            }
+            CloseTabsRule::Duplicates => {
+                let mut candidates: Vec<&Tab> = win_tabs
+                    .tabs
+                    .values()
+                    .filter(|t| t.space_id == space_id)
+                    .collect();
+                // 固定 tab 与活跃 tab 优先保留，其余按位置
+                candidates
+                    .sort_by_key(|t| (!t.is_pinned, !t.is_active, t.folder_id.is_some(), t.index));
+                let mut seen = std::collections::HashSet::new();
+                candidates
+                    .into_iter()
+                    .filter(|t| !seen.insert(normalize_url(&t.url)))
+                    .filter(closable)
+                    .map(|t| t.id)
+                    .collect()
+            }
+        };
+        if tab_ids.is_empty() {
+            return Ok(tab_ids);
+        }
+
+        self.close_tabs(app, window_label, &tab_ids)?;
+        self.emit_tabs_closed(app, window_label, tab_ids.clone());
+
+        Ok(tab_ids)
+    }
+
+    /// 推送一次批量关闭事件
+    fn emit_tabs_closed(&self, app: &AppHandle, window_label: &str, tab_ids: Vec<Uuid>) {
+        emit_tab_list_event(
+            app,
+            window_label,
+            TabListEventKind::TabsClosed { tab_ids },
+            self.get_tab_info_list(window_label),
+            self.get_active_tab_id(window_label),
+        );
+    }
+
+    /// 批量关闭本窗口的 tab，一次性释放 webview，返回被关闭的 tab
+    fn close_tabs(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_ids: &[Uuid],
+    ) -> Result<Vec<Tab>, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let closed_active = win_tabs
+            .active_tab_ids
+            .iter()
+            .find(|id| tab_ids.contains(id))
+            .and_then(|id| win_tabs.tabs.get(id))
+            .cloned();
+        let closed: Vec<Tab> = tab_ids
+            .iter()
+            .filter_map(|id| win_tabs.tabs.remove(id))
+            .collect();
+        win_tabs.histories.retain(|id, _| !tab_ids.contains(id));
+        win_tabs
+            .pending_scroll
+            .retain(|id, _| !tab_ids.contains(id));
+        win_tabs
+            .blocked_popups
+            .retain(|id, _| !tab_ids.contains(id));
+        win_tabs.mru.retain(|id| !tab_ids.contains(id));
+        let pane_count = win_tabs.active_tab_ids.len();
+        win_tabs.active_tab_ids.retain(|id| !tab_ids.contains(id));
+        win_tabs
+            .space_active_tab
+            .retain(|_, id| !tab_ids.contains(id));
+        win_tabs.reindex();
+        // 分屏中的窗格被关闭时，剩余窗格重新布局
+        let split_changed = pane_count > 1 && win_tabs.active_tab_ids.len() != pane_count;
+        if split_changed {
+            win_tabs.split.reset(win_tabs.active_tab_ids.len());
+        }
+        // 作为一组记录到最近关闭列表，撤销时一起恢复
+        let closed_group = closed
+            .iter()
+            .map(|tab| {
+                let snapshot = Tab {
+                    is_active: false,
+                    ..tab.clone()
+                };
+                (snapshot, tab.is_active)
+            })
+            .collect();
+        win_tabs.closed_tabs.push_group(closed_group, now_millis());
+        session::mark_dirty();
+
+        if let Some(window) = app.get_window(window_label) {
+            for tab in closed.iter() {
+                if let Some(webview) = window.get_webview(&tab.id.to_string()) {
+                    let _ = webview.close();
+                }
+            }
+        }
+        // tab 打开的浮动弹窗随之关闭
+        popup::close_popups_of(app, tab_ids);
+        if let Some(closed_active) = closed_active {
+            self.activate_successor(app, window_label, &closed_active);
+        }
+        if split_changed {
+            self.tab_resized(app, window_label);
+        }
+
+        Ok(closed)
+    }
+
+    /// 最近关闭的 tab，最新的在最前
+    pub fn list_closed_tabs(&self, window_label: &str) -> Vec<ClosedTab> {
+        self.windows
+            .get(window_label)
+            .map(|w| w.closed_tabs.list())
+            .unwrap_or_default()
+    }
+
+    /// 重新打开指定的 tab 或最近一次关闭的整组 tab，放回原来的位置，返回激活的 tab
+    pub fn reopen_closed_tab(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        closed_tab_id: Option<Uuid>,
+    ) -> Result<Uuid, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        // 全部恢复成功后才从最近关闭列表中移除
+        let mut group = win_tabs.closed_tabs.peek(closed_tab_id);
+        if group.is_empty() {
+            return Err("No closed tab to reopen".into());
+        }
+        // 按原位置从前往后插入，同级 tab 的相对顺序得以还原
+        group.sort_by_key(|c| {
+            (
+                c.tab.space_id,
+                !c.tab.is_pinned,
+                c.tab.folder_id,
+                c.tab.index,
+            )
+        });
+
+        let current_space_id = self.get_window_space_id(window_label);
+        let mut activate = None;
+        let mut restored = Vec::new();
+        for closed in group {
+            // 原 webview 已销毁，用新的 id 避免 label 冲突
+            let tab_id = self.gen_id();
+            let original_index = closed.tab.index;
+            // 原 space 或文件夹已删除时退回到当前 space 顶层
+            let space_id = if self.spaces.contains(&closed.tab.space_id) {
+                closed.tab.space_id
             } else {
-                return Err("WebView not found".into());
+                current_space_id
+            };
+            let win_tabs = self.window_tabs_mut(window_label);
+            let folder_id = closed.tab.folder_id.filter(|id| {
+                win_tabs
+                    .folders
+                    .get(id)
+                    .is_some_and(|f| f.space_id == space_id)
+            });
+            // webview 在激活时创建
+            win_tabs.tabs.insert(
+                tab_id,
+                Tab {
+                    id: tab_id,
+                    space_id,
+                    folder_id,
+                    index: usize::MAX,
+                    is_hibernated: true,
+                    last_active_at: now_millis(),
+                    ..closed.tab
+                },
+            );
+            win_tabs.reindex();
+            restored.push(tab_id);
+            if let Err(e) = self.move_tab(window_label, tab_id, original_index, folder_id) {
+                // 撤回已插入的 tab，最近关闭列表保持不变
+                let win_tabs = self.window_tabs_mut(window_label);
+                for id in restored {
+                    win_tabs.tabs.remove(&id);
+                }
+                win_tabs.reindex();
+                return Err(e);
+            }
+
+            if closed.was_active || activate.is_none() {
+                activate = Some(tab_id);
             }
         }
-        // 隐藏之前活跃的 tab
-        for old_id in self.active_tab_ids.iter() {
-            if let Some(tab) = self.tabs.get_mut(old_id) {
-                tab.is_active = false;
 
-                if let Some(webview) = window.get_webview(&old_id.to_string()) {
-                    let _ = webview.hide(); // 忽略错误
+        self.window_tabs_mut(window_label)
+            .closed_tabs
+            .take(closed_tab_id);
+        let tab_id = activate.ok_or("No closed tab to reopen")?;
+        self.switch_tab(app, window_label, tab_id)
+    }
+
+    /// 休眠所有窗口中闲置超过 idle 的后台 tab，固定 tab 不休眠，返回被休眠的 tab
+    pub fn hibernate_idle_tabs(&mut self, app: &AppHandle, idle: Duration) -> Vec<Uuid> {
+        let now = now_millis();
+        let idle_ms = idle.as_millis() as u64;
+        let mut hibernated = Vec::new();
+
+        // 隐私窗口的 tab 不休眠
+        for (window_label, win_tabs) in self
+            .windows
+            .iter_mut()
+            .filter(|(_, w)| w.private_store.is_none())
+        {
+            let Some(window) = app.get_window(window_label) else {
+                continue;
+            };
+            for tab in win_tabs.tabs.values_mut().filter(|t| {
+                !t.is_active
+                    && !t.is_pinned
+                    && !t.is_hibernated
+                    && now.saturating_sub(t.last_active_at) >= idle_ms
+            }) {
+                // 只销毁 webview，保留 tab 的地址与标题
+                if let Some(webview) = window.get_webview(&tab.id.to_string()) {
+                    let _ = webview.close();
                 }
+                tab.is_hibernated = true;
+                emit_tab_event(app, window_label, tab.id, TabEventKind::Hibernated);
+                hibernated.push(tab.id);
             }
         }
 
-        // 设置新的 active tab
-        self.active_tab_ids.clear();
-        self.active_tab_ids.push(tab_id);
+        hibernated
+    }
+
+    /// 当前窗口的分屏状态
+    pub fn get_split_view(&self, window_label: &str) -> Result<SplitViewInfo, String> {
+        let win_tabs = self.windows.get(window_label).ok_or("Window not found")?;
+        Ok(SplitViewInfo {
+            layout: win_tabs.split.layout(),
+            ratios: win_tabs.split.ratios(),
+            tab_ids: win_tabs.active_tab_ids.clone(),
+        })
+    }
+
+    /// 把 tab 加入分屏，作为最后一个窗格显示
+    pub fn add_tab_to_split(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> Result<SplitViewInfo, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let tab = win_tabs.tabs.get(&tab_id).ok_or("Tab not found")?;
+        if win_tabs.active_tab_ids.contains(&tab_id) {
+            return Err("Tab is already in the split view".into());
+        }
+        if tab.space_id != win_tabs.space_id {
+            return Err("Tab belongs to another space".into());
+        }
+        if win_tabs.active_tab_ids.len() >= MAX_SPLIT_PANES {
+            return Err(format!(
+                "Split view supports up to {} tabs",
+                MAX_SPLIT_PANES
+            ));
+        }
+
+        let window = app.get_window(window_label).ok_or("Window not found")?;
+        let webview = win_tabs.ensure_webview(app, &window, &tab_id)?;
+        let _ = webview.show();
+
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            tab.is_active = true;
+            tab.is_hibernated = false;
+            tab.last_active_at = now_millis();
+        }
+        win_tabs.active_tab_ids.push(tab_id);
+        win_tabs.touch_mru(tab_id);
+        win_tabs.split.reset(win_tabs.active_tab_ids.len());
 
         self.tab_resized(app, window_label);
+        self.get_split_view(window_label)
+    }
 
-        Ok(tab_id)
+    /// 把 tab 移出分屏，至少保留一个窗格
+    pub fn remove_tab_from_split(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> Result<SplitViewInfo, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        if !win_tabs.active_tab_ids.contains(&tab_id) {
+            return Err("Tab is not in the split view".into());
+        }
+        if win_tabs.active_tab_ids.len() <= 1 {
+            return Err("Cannot remove the last tab from the split view".into());
+        }
+
+        win_tabs.active_tab_ids.retain(|id| *id != tab_id);
+        win_tabs.split.reset(win_tabs.active_tab_ids.len());
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            tab.is_active = false;
+            tab.last_active_at = now_millis();
+        }
+        let space_id = win_tabs.space_id;
+        win_tabs
+            .space_active_tab
+            .insert(space_id, win_tabs.active_tab_ids[0]);
+        if let Some(webview) = app
+            .get_window(window_label)
+            .and_then(|w| w.get_webview(&tab_id.to_string()))
+        {
+            let _ = webview.hide();
+        }
+
+        self.tab_resized(app, window_label);
+        self.get_split_view(window_label)
     }
 
-    pub fn close_tab(
+    /// 交换两个窗格的位置
+    pub fn swap_split_panes(
         &mut self,
         app: &AppHandle,
         window_label: &str,
         tab_id: Uuid,
+        other_tab_id: Uuid,
+    ) -> Result<SplitViewInfo, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let position = |id: &Uuid| {
+            win_tabs
+                .active_tab_ids
+                .iter()
+                .position(|t| t == id)
+                .ok_or("Tab is not in the split view")
+        };
+        let (a, b) = (position(&tab_id)?, position(&other_tab_id)?);
+        win_tabs.active_tab_ids.swap(a, b);
+
+        self.tab_resized(app, window_label);
+        self.get_split_view(window_label)
+    }
+
+    /// 切换分屏布局，分隔条恢复均分
+    pub fn set_split_layout(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        layout: SplitLayout,
+    ) -> Result<SplitViewInfo, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let pane_count = win_tabs.active_tab_ids.len();
+        win_tabs.split.set_layout(layout, pane_count);
+
+        self.tab_resized(app, window_label);
+        self.get_split_view(window_label)
+    }
+
+    /// 调整分隔条位置
+    pub fn set_split_ratios(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        ratios: Vec<f64>,
+    ) -> Result<SplitViewInfo, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let pane_count = win_tabs.active_tab_ids.len();
+        win_tabs.split.set_ratios(ratios, pane_count)?;
+
+        self.tab_resized(app, window_label);
+        self.get_split_view(window_label)
+    }
+
+    /// 在窗口当前 space 下创建文件夹
+    pub fn create_folder(
+        &mut self,
+        window_label: &str,
+        name: &str,
+        parent_id: Option<Uuid>,
+    ) -> Result<Folder, String> {
+        let win_tabs = self.window_tabs_mut(window_label);
+        let folder = win_tabs
+            .folders
+            .create(win_tabs.space_id, parent_id, name)?;
+        session::mark_dirty();
+        Ok(folder)
+    }
+
+    pub fn rename_folder(
+        &mut self,
+        window_label: &str,
+        folder_id: Uuid,
+        name: &str,
+    ) -> Result<(), String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        win_tabs.folders.rename(&folder_id, name)?;
+        session::mark_dirty();
+        Ok(())
+    }
+
+    pub fn set_folder_collapsed(
+        &mut self,
+        window_label: &str,
+        folder_id: Uuid,
+        collapsed: bool,
     ) -> Result<(), String> {
-        let window: Window = app.get_window(window_label).ok_or("Window not found")?;
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        win_tabs.folders.set_collapsed(&folder_id, collapsed)?;
+        session::mark_dirty();
+        Ok(())
+    }
 
-        if let Some(webview) = window.get_webview(&tab_id.to_string()) {
-            let _ = webview.close();
+    /// 删除文件夹；close_tabs 为 true 时关闭整个子树的 tab，否则内容上移到父级
+    pub fn delete_folder(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        folder_id: Uuid,
+        close_tabs: bool,
+    ) -> Result<(), String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        if !win_tabs.folders.contains(&folder_id) {
+            return Err("Folder not found".into());
         }
 
-        self.tabs.remove(&tab_id);
-        self.active_tab_ids.retain(|id| *id != tab_id);
+        if close_tabs {
+            let folder_ids = win_tabs.folders.remove_subtree(&folder_id);
+            let tab_ids: Vec<Uuid> = win_tabs
+                .tabs
+                .values()
+                .filter(|t| t.folder_id.is_some_and(|id| folder_ids.contains(&id)))
+                .map(|t| t.id)
+                .collect();
+            self.close_tabs(app, window_label, &tab_ids)?;
+            self.emit_tabs_closed(app, window_label, tab_ids);
+            return Ok(());
+        }
 
-        let mut tab_list: Vec<_> = self.tabs.values_mut().collect();
-        tab_list.sort_by_key(|t| t.index);
-        for (new_index, tab) in tab_list.iter_mut().enumerate() {
-            tab.index = new_index;
+        let Some(folder) = win_tabs.folders.remove_lift_children(&folder_id) else {
+            return Err("Folder not found".into());
+        };
+        // tab 上移到父级，排在父级已有 tab 之后
+        let base_index = win_tabs.group_len(&folder.space_id, false, folder.parent_id);
+        let mut lifted: Vec<&mut Tab> = win_tabs
+            .tabs
+            .values_mut()
+            .filter(|t| t.folder_id == Some(folder_id))
+            .collect();
+        lifted.sort_by_key(|t| t.index);
+        for (offset, tab) in lifted.into_iter().enumerate() {
+            tab.folder_id = folder.parent_id;
+            tab.index = base_index + offset;
+        }
+        win_tabs.reindex();
+        session::mark_dirty();
+
+        Ok(())
+    }
+
+    /// 把 tab 移入文件夹末尾，folder_id 为 None 时移出到顶层
+    pub fn move_tab_to_folder(
+        &mut self,
+        window_label: &str,
+        tab_id: Uuid,
+        folder_id: Option<Uuid>,
+    ) -> Result<(), String> {
+        let current_folder_id = self
+            .windows
+            .get(window_label)
+            .and_then(|w| w.tabs.get(&tab_id))
+            .map(|t| t.folder_id)
+            .ok_or("Tab not found")?;
+        if current_folder_id == folder_id {
+            return Ok(());
+        }
+        self.move_tab(window_label, tab_id, usize::MAX, folder_id)?;
+
+        Ok(())
+    }
+
+    /// 移动 tab 到指定文件夹（None 为顶层）的指定位置，同时重排其余 tab，返回新的排序
+    pub fn move_tab(
+        &mut self,
+        window_label: &str,
+        tab_id: Uuid,
+        new_index: usize,
+        folder_id: Option<Uuid>,
+    ) -> Result<TabInfoList, String> {
+        let win_tabs = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?;
+        let tab = win_tabs.tabs.get(&tab_id).ok_or("Tab not found")?;
+        let (space_id, is_pinned) = (tab.space_id, tab.is_pinned);
+        if let Some(folder_id) = folder_id {
+            if is_pinned {
+                return Err("Pinned tab cannot be moved into a folder".into());
+            }
+            let folder = win_tabs.folders.get(&folder_id).ok_or("Folder not found")?;
+            if folder.space_id != space_id {
+                return Err("Folder belongs to another space".into());
+            }
+        }
+
+        // 目标分组内除自身外的现有顺序
+        let mut siblings: Vec<&Tab> = win_tabs
+            .tabs
+            .values()
+            .filter(|t| {
+                t.id != tab_id
+                    && t.space_id == space_id
+                    && t.is_pinned == is_pinned
+                    && t.folder_id == folder_id
+            })
+            .collect();
+        siblings.sort_by_key(|t| t.index);
+        let mut order: Vec<Uuid> = siblings.iter().map(|t| t.id).collect();
+        order.insert(new_index.min(order.len()), tab_id);
+
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            tab.folder_id = folder_id;
+        }
+        for (index, id) in order.iter().enumerate() {
+            if let Some(tab) = win_tabs.tabs.get_mut(id) {
+                tab.index = index;
+            }
+        }
+        // 压缩原分组留下的空位
+        win_tabs.reindex();
+        session::mark_dirty();
+
+        Ok(self.get_tab_info_list(window_label))
+    }
+
+    /// 切换窗口显示的 space，并激活该 space 最近使用的 tab
+    pub fn switch_space(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        space_id: Uuid,
+    ) -> Result<Option<Uuid>, String> {
+        if !self.spaces.contains(&space_id) {
+            return Err("Space not found".into());
+        }
+        let win_tabs = self.window_tabs_mut(window_label);
+        let target = win_tabs
+            .space_active_tab
+            .get(&space_id)
+            .copied()
+            .filter(|id| win_tabs.tabs.contains_key(id))
+            .or_else(|| win_tabs.first_tab_in_space(&space_id));
+        win_tabs.space_id = space_id;
+        session::mark_dirty();
+
+        match target {
+            Some(tab_id) => self.switch_tab(app, window_label, tab_id).map(Some),
+            None => {
+                // 空 space，只隐藏旧 space 的 webview
+                let window = app.get_window(window_label).ok_or("Window not found")?;
+                self.window_tabs_mut(window_label).hide_active(&window);
+                Ok(None)
+            }
         }
+    }
+
+    /// 删除 space 及其在所有窗口中的 tab
+    pub fn delete_space(&mut self, app: &AppHandle, space_id: Uuid) -> Result<(), String> {
+        self.spaces.remove(&space_id)?;
+        let fallback_space_id = self.spaces.default_space_id();
+
+        for window_label in self.window_labels() {
+            let tab_ids: Vec<Uuid> = self.windows[&window_label]
+                .tabs
+                .values()
+                .filter(|t| t.space_id == space_id)
+                .map(|t| t.id)
+                .collect();
+            if !tab_ids.is_empty() && self.close_tabs(app, &window_label, &tab_ids).is_ok() {
+                self.emit_tabs_closed(app, &window_label, tab_ids);
+            }
+
+            if self.windows[&window_label].space_id == space_id {
+                let _ = self.switch_space(app, &window_label, fallback_space_id);
+            }
+            if let Some(win_tabs) = self.windows.get_mut(&window_label) {
+                win_tabs.space_active_tab.remove(&space_id);
+                win_tabs.folders.remove_space(&space_id);
+            }
+        }
+        session::mark_dirty();
 
         Ok(())
     }
@@ -176,56 +1542,426 @@ impl TabManager {
         tab_id: Uuid,
         action: TabNavigation,
     ) -> anyhow::Result<()> {
+        let tab = self
+            .windows
+            .get_mut(window_label)
+            .and_then(|w| w.tabs.get_mut(&tab_id))
+            .context("Tab not found")?;
+        // 休眠的 tab 只记录新地址，切换时再加载
+        if tab.is_hibernated {
+            if let TabNavigation::NavigateTo(url) = action {
+                tab.url = url;
+                session::mark_dirty();
+                return Ok(());
+            }
+            anyhow::bail!("Tab is hibernated");
+        }
         let window: Window = app.get_window(window_label).context("Window not found")?;
         let webview: Webview = window
             .get_webview(&tab_id.to_string())
             .context("Webview not found")?;
+        let history = self
+            .windows
+            .get_mut(window_label)
+            .context("Window not found")?
+            .histories
+            .entry(tab_id)
+            .or_default();
 
         match action {
-            TabNavigation::Back => webview_go_back(&webview)?,
+            TabNavigation::Back => {
+                anyhow::ensure!(history.can_go_back(), "No previous page");
+                let index = history.current_index() - 1;
+                webview_traverse(&webview, history.traverse(index))?
+            }
             TabNavigation::Reload => webview_reload(&webview)?,
-            TabNavigation::Forward => webview_go_forward(&webview)?,
+            TabNavigation::Forward => {
+                anyhow::ensure!(history.can_go_forward(), "No next page");
+                let index = history.current_index() + 1;
+                webview_traverse(&webview, history.traverse(index))?
+            }
+            TabNavigation::GoToEntry(index) => webview_traverse(&webview, history.traverse(index))?,
             TabNavigation::NavigateTo(url) => webview_navigate_to(&webview, &url)?,
         }
 
         Ok(())
     }
 
+    /// tab 的导航历史
+    pub fn get_tab_history(
+        &self,
+        window_label: &str,
+        tab_id: Uuid,
+    ) -> Result<TabHistoryInfo, String> {
+        let win_tabs = self.windows.get(window_label).ok_or("Window not found")?;
+        if !win_tabs.tabs.contains_key(&tab_id) {
+            return Err("Tab not found".into());
+        }
+        Ok(win_tabs
+            .histories
+            .get(&tab_id)
+            .map(|h| h.info())
+            .unwrap_or_else(|| TabHistory::default().info()))
+    }
+
+    /// webview 主 frame 开始加载新页面
+    pub fn record_navigation(&mut self, tab_id: Uuid, url: &str) {
+        if let Some(win_tabs) = self.tab_window_mut(&tab_id) {
+            win_tabs
+                .histories
+                .entry(tab_id)
+                .or_default()
+                .navigation_requested(url);
+        }
+    }
+
+    /// webview 页面加载完成，同步 tab 当前地址，返回地址是否变化
+    pub fn record_page_load(&mut self, tab_id: Uuid, url: &str) -> bool {
+        self.record_url_change(tab_id, url, false)
+    }
+
+    /// 页面地址变化（含单页应用的 history API），replace 为 true 时替换当前历史记录
+    pub fn record_url_change(&mut self, tab_id: Uuid, url: &str, replace: bool) -> bool {
+        let Some(win_tabs) = self.tab_window_mut(&tab_id) else {
+            return false;
+        };
+        let history = win_tabs.histories.entry(tab_id).or_default();
+        if replace {
+            history.replace_current(url);
+        } else {
+            history.commit(url);
+        }
+        match win_tabs.tabs.get_mut(&tab_id) {
+            Some(tab) if tab.url != url => {
+                tab.url = url.to_string();
+                session::mark_dirty();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 缩放 tab，有 origin 的页面按站点记住缩放比例并同步到该站点的所有 tab，返回新的比例
+    pub fn zoom_tab(
+        &mut self,
+        app: &AppHandle,
+        window_label: &str,
+        tab_id: Uuid,
+        action: ZoomAction,
+    ) -> Result<f64, String> {
+        let tab = self
+            .windows
+            .get(window_label)
+            .and_then(|w| w.tabs.get(&tab_id))
+            .ok_or("Tab not found")?;
+        let level = zoom::next_zoom(tab.zoom, action);
+
+        // 隐私窗口不记录站点缩放，只缩放当前 tab
+        let origin = zoom::site_origin(&tab.url).filter(|_| !self.is_private_window(window_label));
+        let Some(origin) = origin else {
+            self.apply_zoom(app, &tab_id, level);
+            return Ok(level);
+        };
+        zoom::save_site_zoom(app, &origin, level).map_err(|e| format!("{:#}", e))?;
+        let site_tab_ids: Vec<Uuid> = self
+            .windows
+            .values()
+            .filter(|w| w.private_store.is_none())
+            .flat_map(|w| w.tabs.values())
+            .filter(|t| zoom::site_origin(&t.url).as_deref() == Some(origin.as_str()))
+            .map(|t| t.id)
+            .collect();
+        for id in site_tab_ids {
+            self.apply_zoom(app, &id, level);
+        }
+
+        Ok(level)
+    }
+
+    /// 页面开始加载时应用目标站点记住的缩放比例
+    pub fn apply_site_zoom(&mut self, app: &AppHandle, tab_id: Uuid, url: &str) {
+        let is_private = self.is_private_tab(&tab_id);
+        let Some(tab) = self
+            .tab_window_mut(&tab_id)
+            .and_then(|w| w.tabs.get(&tab_id))
+        else {
+            return;
+        };
+        // 隐私窗口沿用 tab 自己的缩放
+        let level = match zoom::site_origin(url) {
+            Some(origin) if !is_private => zoom::site_zoom(&origin),
+            _ => tab.zoom,
+        };
+        self.apply_zoom(app, &tab_id, level);
+    }
+
+    /// 设置 tab 的缩放比例，已加载的 webview 立即生效，变化时推送事件
+    fn apply_zoom(&mut self, app: &AppHandle, tab_id: &Uuid, level: f64) {
+        let Some(window_label) = self.tab_window_label(tab_id) else {
+            return;
+        };
+        let Some(tab) = self
+            .tab_window_mut(tab_id)
+            .and_then(|w| w.tabs.get_mut(tab_id))
+        else {
+            return;
+        };
+        let changed = tab.zoom != level;
+        tab.zoom = level;
+        // 重建的 webview 缩放为默认值，即使比例没变也需要重新设置
+        if let Some(webview) = app
+            .get_window(&window_label)
+            .and_then(|w| w.get_webview(&tab_id.to_string()))
+        {
+            if let Err(e) = webview.set_zoom(level) {
+                eprintln!("[Tab:zoom] Failed to zoom tab {}: {}", tab_id, e);
+            }
+        }
+        if changed {
+            session::mark_dirty();
+            emit_tab_event(
+                app,
+                &window_label,
+                *tab_id,
+                TabEventKind::ZoomChanged { zoom: level },
+            );
+        }
+    }
+
+    /// 记录 tab 当前页面被拦截的弹窗
+    pub fn record_blocked_popup(&mut self, tab_id: Uuid, popup: BlockedPopup) {
+        if let Some(win_tabs) = self.tab_window_mut(&tab_id) {
+            popup::push_blocked(win_tabs.blocked_popups.entry(tab_id).or_default(), popup);
+        }
+    }
+
+    /// 页面开始加载新文档时清空被拦截的弹窗
+    pub fn clear_blocked_popups(&mut self, tab_id: Uuid) {
+        if let Some(win_tabs) = self.tab_window_mut(&tab_id) {
+            win_tabs.blocked_popups.remove(&tab_id);
+        }
+    }
+
+    pub fn get_blocked_popups(&self, window_label: &str, tab_id: &Uuid) -> Vec<BlockedPopup> {
+        self.windows
+            .get(window_label)
+            .and_then(|w| w.blocked_popups.get(tab_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// 取出 tab 记录的被拦截弹窗，只能打开确实被拦截过的地址
+    pub fn take_blocked_popup(
+        &mut self,
+        window_label: &str,
+        tab_id: &Uuid,
+        url: &str,
+    ) -> Result<BlockedPopup, String> {
+        let blocked = self
+            .windows
+            .get_mut(window_label)
+            .ok_or("Window not found")?
+            .blocked_popups
+            .get_mut(tab_id)
+            .ok_or("Blocked popup not found")?;
+        let position = blocked
+            .iter()
+            .position(|p| p.url == url)
+            .ok_or("Blocked popup not found")?;
+        Ok(blocked.remove(position))
+    }
+
+    /// 记录复制 tab 待恢复的滚动位置，url 为源页面地址
+    pub fn set_pending_scroll(&mut self, tab_id: Uuid, url: String, x: f64, y: f64) {
+        if let Some(win_tabs) = self.tab_window_mut(&tab_id) {
+            win_tabs.pending_scroll.insert(tab_id, (url, x, y));
+        }
+    }
+
+    /// 页面加载完成时取出待恢复的滚动位置，只在加载的仍是源页面时返回
+    pub fn take_pending_scroll(&mut self, tab_id: Uuid, url: &str) -> Option<(f64, f64)> {
+        let (pending_url, x, y) = self
+            .tab_window_mut(&tab_id)?
+            .pending_scroll
+            .remove(&tab_id)?;
+        (pending_url == url).then_some((x, y))
+    }
+
+    /// 更新 tab 图标，返回是否变化
+    pub fn set_favicon(&mut self, tab_id: Uuid, favicon: Option<String>) -> bool {
+        let Some(tab) = self
+            .tab_window_mut(&tab_id)
+            .and_then(|w| w.tabs.get_mut(&tab_id))
+        else {
+            return false;
+        };
+        if tab.favicon == favicon {
+            return false;
+        }
+        tab.favicon = favicon;
+        session::mark_dirty();
+        true
+    }
+
+    /// tab 所在窗口的 label
+    pub fn tab_window_label(&self, tab_id: &Uuid) -> Option<String> {
+        self.windows
+            .iter()
+            .find(|(_, w)| w.tabs.contains_key(tab_id))
+            .map(|(label, _)| label.clone())
+    }
+
+    /// webview 页面标题变化，同步 tab 名称
+    pub fn record_title(&mut self, tab_id: Uuid, title: &str) {
+        let Some(win_tabs) = self.tab_window_mut(&tab_id) else {
+            return;
+        };
+        if let Some(tab) = win_tabs.tabs.get_mut(&tab_id) {
+            // 空标题保留原名称
+            if !title.is_empty() && tab.name != title {
+                tab.name = title.to_string();
+                session::mark_dirty();
+            }
+        }
+        win_tabs
+            .histories
+            .entry(tab_id)
+            .or_default()
+            .set_title(title);
+    }
+
+    /// 查找 tab 所在窗口，webview 回调不知道 tab 当前所在的窗口
+    fn tab_window_mut(&mut self, tab_id: &Uuid) -> Option<&mut WindowTabs> {
+        self.windows
+            .values_mut()
+            .find(|w| w.tabs.contains_key(tab_id))
+    }
+
     fn gen_id(&self) -> Uuid {
         Uuid::new_v4()
     }
 
+    /// 获取窗口 tab 状态，不存在时以默认 space 创建
+    fn window_tabs_mut(&mut self, window_label: &str) -> &mut WindowTabs {
+        let default_space_id = self.spaces.default_space_id();
+        self.windows
+            .entry(window_label.to_string())
+            .or_insert_with(|| WindowTabs::new(default_space_id))
+    }
+
     pub fn tab_resized(&self, app: &AppHandle, window_label: &str) {
         let window = match app.get_window(window_label) {
             Some(w) => w,
             // 没找到窗口，直接返回
             None => return,
         };
+        let win_tabs = match self.windows.get(window_label) {
+            Some(w) => w,
+            None => return,
+        };
 
         let window_size = window.inner_size().unwrap();
         let scale_factor = get_window_scale_factor(&app, window_label).unwrap();
         let sidebar_width = get_sidebar_width(window_label);
 
-        for active_tab_id in self.active_tab_ids.iter() {
+        let panes = win_tabs.split.pane_rects(win_tabs.active_tab_ids.len());
+        for (active_tab_id, pane) in win_tabs.active_tab_ids.iter().zip(panes.iter()) {
             let webview = window.get_webview(&active_tab_id.to_string()).unwrap();
             let (position, size) =
-                calc_webview_geometry(&active_tab_id, window_size, scale_factor, sidebar_width);
+                calc_webview_geometry(pane, window_size, scale_factor, sidebar_width);
             set_webview_properties(&webview, position, size);
         }
         overlay_resized(app, window_label);
     }
 }
 
+/// 创建 tab 对应的子 webview 并挂载到宿主窗口，初始隐藏
+fn attach_webview(
+    app: &AppHandle,
+    window_label: &str,
+    tab_id: &Uuid,
+    url: &str,
+    private_store: Option<Uuid>,
+) -> Result<(), String> {
+    // 获取宿主 Window
+    let window: Window = app.get_window(window_label).ok_or("Window not found")?;
+    // 窗口尺寸
+    let position = LogicalPosition::new(get_sidebar_width(window_label), 50.);
+    let size = LogicalSize::new(0., 0.);
+
+    let webview_builder = create_webview_builder(app, tab_id, url, private_store);
+    window
+        .add_child(webview_builder, position, size)
+        .map_err(|e| e.to_string())
+        .inspect(|wv| {
+            #[cfg(target_os = "macos")]
+            let (app, tab_id) = (app.clone(), *tab_id);
+            let _ = wv.with_webview(move |webview| {
+                #[cfg(target_os = "macos")]
+                unsafe {
+                    set_webview_corner_radius(webview.inner(), 12.0);
+                    crash::watch(&app, tab_id, webview.inner());
+                }
+            });
+
+            let _ = wv.hide();
+        })?;
+
+    Ok(())
+}
+
+/// 判断重复 tab 使用的地址：忽略 fragment 与末尾的 /
+fn normalize_url(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.set_fragment(None);
+            parsed.as_str().trim_end_matches('/').to_string()
+        }
+        Err(_) => url.trim_end_matches('/').to_string(),
+    }
+}
+
+/// 当前毫秒时间戳
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// 按窗格占比计算 webview 位置与尺寸，相邻窗格之间留出 TAB_MARGIN 间距
 fn calc_webview_geometry(
-    _tab_id: &Uuid,
+    pane: &PaneRect,
     window_size: tauri::PhysicalSize<u32>,
     scale_factor: f64,
     sidebar_width: f64,
 ) -> (LogicalPosition<f64>, LogicalSize<f64>) {
-    let position = LogicalPosition::new(sidebar_width, TAB_MARGIN);
+    // 内容区：侧栏右侧，四周留边距
+    let area_width = window_size.width as f64 / scale_factor - sidebar_width - TAB_MARGIN;
+    let area_height = window_size.height as f64 / scale_factor - TAB_MARGIN * 2.0;
+    let half_gap = TAB_MARGIN / 2.0;
+
+    // 只在与其他窗格相邻的一侧收缩
+    let left = if pane.x > 0.0 { half_gap } else { 0.0 };
+    let right = if pane.x + pane.width < 1.0 {
+        half_gap
+    } else {
+        0.0
+    };
+    let top = if pane.y > 0.0 { half_gap } else { 0.0 };
+    let bottom = if pane.y + pane.height < 1.0 {
+        half_gap
+    } else {
+        0.0
+    };
+
+    let position = LogicalPosition::new(
+        sidebar_width + pane.x * area_width + left,
+        TAB_MARGIN + pane.y * area_height + top,
+    );
     let size = LogicalSize::new(
-        window_size.width as f64 / scale_factor - sidebar_width - TAB_MARGIN,
-        window_size.height as f64 / scale_factor - TAB_MARGIN * 2.0,
+        pane.width * area_width - left - right,
+        pane.height * area_height - top - bottom,
     );
     (position, size)
 }
@@ -235,17 +1971,13 @@ fn webview_reload(webview: &Webview) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn webview_go_back(webview: &Webview) -> anyhow::Result<()> {
-    webview
-        .eval("window.history.back()")
-        .context("Failed to go back in webview history")?;
-    Ok(())
-}
-
-fn webview_go_forward(webview: &Webview) -> anyhow::Result<()> {
-    webview
-        .eval("window.history.forward()")
-        .context("Failed to go forward in webview history")?;
+fn webview_traverse(webview: &Webview, traversal: Result<Traversal, String>) -> anyhow::Result<()> {
+    match traversal.map_err(anyhow::Error::msg)? {
+        Traversal::Go(delta) => webview
+            .eval(format!("window.history.go({})", delta))
+            .context("Failed to traverse webview history")?,
+        Traversal::Load(url) => webview_navigate_to(webview, &url)?,
+    }
     Ok(())
 }
 
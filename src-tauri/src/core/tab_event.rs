@@ -0,0 +1,134 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+use crate::core::tab::TabInfoList;
+
+// 前端监听的 tab 事件名
+pub const TAB_EVENT: &str = "tab_event";
+// payload 结构版本，字段不兼容变更时递增
+pub const TAB_EVENT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum TabEventKind {
+    TitleChanged {
+        title: String,
+    },
+    UrlChanged {
+        url: String,
+    },
+    LoadStarted {
+        url: String,
+    },
+    LoadFinished {
+        url: String,
+    },
+    FaviconChanged {
+        favicon: Option<String>,
+    },
+    // 0..1，平台没有细粒度进度，按页面加载阶段估算
+    LoadProgress {
+        progress: f64,
+    },
+    AudioChanged {
+        is_playing: bool,
+    },
+    ZoomChanged {
+        zoom: f64,
+    },
+    // 闲置的后台 tab 被休眠，webview 已卸载
+    Hibernated,
+    // 拦截了页面弹窗，可允许一次或始终允许该站点
+    PopupBlocked {
+        url: String,
+        origin: String,
+    },
+    // 页内查找结果：第 active_match 个，共 match_count 个
+    FindResult {
+        query: String,
+        active_match: u32,
+        match_count: u32,
+    },
+    // 网页内容进程崩溃或被系统终止
+    Crashed,
+    Closed,
+}
+
+/// 推送给窗口的 tab 事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabEvent {
+    pub version: u32,
+    pub window_label: String,
+    pub tab_id: Uuid,
+    pub event: TabEventKind,
+}
+
+/// 只向 tab 所在窗口推送事件，发送失败只记录日志
+pub fn emit_tab_event(app: &AppHandle, window_label: &str, tab_id: Uuid, event: TabEventKind) {
+    let payload = TabEvent {
+        version: TAB_EVENT_VERSION,
+        window_label: window_label.to_string(),
+        tab_id,
+        event,
+    };
+    if let Err(e) = app.emit_to(window_label, TAB_EVENT, &payload) {
+        eprintln!(
+            "[TabEvent:emit] Failed to emit {:?} for tab {}: {}",
+            payload.event, tab_id, e
+        );
+    }
+}
+
+// 前端监听的 tab 列表事件名，批量变更只推送一次
+pub const TAB_LIST_EVENT: &str = "tab_list_event";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum TabListEventKind {
+    // 一次操作关闭了多个 tab，可作为一组撤销
+    TabsClosed { tab_ids: Vec<Uuid> },
+}
+
+/// 推送给窗口的 tab 列表事件，附带变更后的完整列表
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabListEvent {
+    pub version: u32,
+    pub window_label: String,
+    pub event: TabListEventKind,
+    pub tab_info_list: TabInfoList,
+    pub active_tab_id: Option<Uuid>,
+}
+
+/// 推送 tab 列表事件，发送失败只记录日志
+pub fn emit_tab_list_event(
+    app: &AppHandle,
+    window_label: &str,
+    event: TabListEventKind,
+    tab_info_list: TabInfoList,
+    active_tab_id: Option<Uuid>,
+) {
+    let payload = TabListEvent {
+        version: TAB_EVENT_VERSION,
+        window_label: window_label.to_string(),
+        event,
+        tab_info_list,
+        active_tab_id,
+    };
+    if let Err(e) = app.emit_to(window_label, TAB_LIST_EVENT, &payload) {
+        eprintln!(
+            "[TabEvent:emit] Failed to emit {:?} for window {}: {}",
+            payload.event, window_label, e
+        );
+    }
+}
@@ -1,22 +1,34 @@
-use serde::Serialize;
-use tauri::webview::{NewWindowFeatures, NewWindowResponse, WebviewBuilder};
-use tauri::{AppHandle, Emitter, Runtime, Url, WebviewUrl};
+use once_cell::sync::OnceCell;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use tauri::webview::{NewWindowFeatures, NewWindowResponse, PageLoadEvent, WebviewBuilder};
+use tauri::{AppHandle, Manager, Url, WebviewUrl, Wry};
 use uuid::Uuid;
 
-#[derive(Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-struct TabUpdate {
-    tab_id: String,
-    title: String,
-    url: Url,
-    event: String,
-}
+use crate::core::bridge::{
+    bridge_script, parse_bridge_message, scroll_to_script, BridgeMessage, BRIDGE_SCHEME,
+};
+use crate::core::favicon;
+use crate::core::find::FIND_SCRIPT;
+use crate::core::popup::{self, BlockedPopup, NewWindowAction};
+use crate::core::private_data;
+use crate::core::tab::TabManager;
+use crate::core::tab_event::{emit_tab_event, TabEventKind};
+
+// 页面开始加载时上报的进度
+const LOAD_STARTED_PROGRESS: f64 = 0.1;
 
-pub fn create_webview_builder<R: Runtime>(
+type TabManagerUpdate = Box<dyn FnOnce(&AppHandle, &mut TabManager) + Send>;
+
+// webview 回调产生的 TabManager 更新队列，由单个线程按顺序执行
+static TAB_MANAGER_UPDATES: OnceCell<Sender<TabManagerUpdate>> = OnceCell::new();
+
+pub fn create_webview_builder(
     app: &AppHandle,
     tab_id: &Uuid,
     url: &str,
-) -> WebviewBuilder<R> {
+    private_store: Option<Uuid>,
+) -> WebviewBuilder<Wry> {
     let parsed_url = match url.parse::<url::Url>() {
         Ok(u) => u,
         Err(e) => {
@@ -29,51 +41,342 @@ pub fn create_webview_builder<R: Runtime>(
     };
 
     let parsed_url_clone = parsed_url.clone();
-    let webview_builder =
+    // 桥接令牌只写入主 frame 的脚本，用于区分主 frame 与子 frame 发来的消息
+    let bridge_token = Uuid::new_v4().simple().to_string();
+    // 手势令牌写入所有 frame 的脚本，只用于记录用户手势
+    let gesture_token = Uuid::new_v4().simple().to_string();
+    let private = private_store.is_some();
+    let mut webview_builder =
         WebviewBuilder::new(&tab_id.to_string(), WebviewUrl::External(parsed_url_clone))
             .user_agent(get_desktop_user_agent().as_str())
+            .initialization_script(bridge_script(&bridge_token))
+            .initialization_script(FIND_SCRIPT)
+            .initialization_script_for_all_frames(popup::gesture_script(&gesture_token))
             .on_document_title_changed({
                 let app = app.clone();
-                let parsed_url = parsed_url.clone();
+                let tab_id = *tab_id;
                 move |webview, new_title| {
-                    // println!("新标题: {}", new_title);
-
-                    let payload = TabUpdate {
-                        tab_id: webview.label().to_string(),
-                        title: new_title,
-                        url: parsed_url.clone(),
-                        event: "titleChanged".to_string(),
-                    };
-
-                    // TODO 临时实现，后续统一规划tab状态与浏览历史，tab推送事件也可采用window.emit
-                    app.emit("tab_update", payload).unwrap();
+                    let window_label = webview.window().label().to_string();
+                    emit_tab_event(
+                        &app,
+                        &window_label,
+                        tab_id,
+                        TabEventKind::TitleChanged {
+                            title: new_title.clone(),
+                        },
+                    );
+                    update_tab_manager(&app, move |_, tm| tm.record_title(tab_id, &new_title));
+                }
+            })
+            .on_navigation({
+                let app = app.clone();
+                let tab_id = *tab_id;
+                move |url| {
+                    // 页面桥接消息，拦截后不导航
+                    if url.scheme() == BRIDGE_SCHEME {
+                        if url.host_str() == Some(popup::GESTURE_HOST) {
+                            if url
+                                .query_pairs()
+                                .any(|(k, v)| k == "t" && v == gesture_token)
+                            {
+                                popup::record_gesture(tab_id);
+                            }
+                            return false;
+                        }
+                        match parse_bridge_message(url, &bridge_token) {
+                            Some(BridgeMessage::Favicon { href, data }) => {
+                                // 缓存按站点存储，站点取 webview 当前地址，不信任页面上报
+                                let origin = page_url(&app, tab_id)
+                                    .map(|u| u.origin())
+                                    .filter(|origin| origin.is_tuple())
+                                    .map(|origin| origin.ascii_serialization());
+                                if let Some(origin) = origin {
+                                    handle_favicon_message(
+                                        &app, tab_id, origin, href, data, private,
+                                    )
+                                }
+                            }
+                            Some(message) => {
+                                // 页面地址在主线程回调中直接从 webview 读取
+                                let page_url = page_url(&app, tab_id);
+                                update_tab_manager(&app, move |app, tm| {
+                                    handle_bridge_message(app, tm, tab_id, page_url, message)
+                                })
+                            }
+                            None => {}
+                        }
+                        return false;
+                    }
+                    true
                 }
             })
             .on_page_load({
                 let app = app.clone();
-                move |webview, pagleload| {
-                    // println!("页面加载: {:?}", pagleload);
-
-                    let payload = TabUpdate {
-                        tab_id: webview.label().to_string(),
-                        title: "".to_string(),
-                        url: pagleload.url().clone(),
-                        event: format!("{:?}", pagleload.event()),
-                    };
-
-                    app.emit("tab_update", payload).unwrap();
+                let tab_id = *tab_id;
+                move |webview, payload| {
+                    let window_label = webview.window().label().to_string();
+                    let url = payload.url().to_string();
+                    match payload.event() {
+                        PageLoadEvent::Started => {
+                            // on_navigation 也会收到子 frame 的导航，历史只在主 frame 开始加载时记录
+                            update_tab_manager(&app, {
+                                let url = url.clone();
+                                move |app, tm| {
+                                    tm.record_navigation(tab_id, &url);
+                                    tm.clear_blocked_popups(tab_id);
+                                    tm.apply_site_zoom(app, tab_id, &url)
+                                }
+                            });
+                            emit_tab_event(
+                                &app,
+                                &window_label,
+                                tab_id,
+                                TabEventKind::LoadStarted { url },
+                            );
+                            emit_tab_event(
+                                &app,
+                                &window_label,
+                                tab_id,
+                                TabEventKind::LoadProgress {
+                                    progress: LOAD_STARTED_PROGRESS,
+                                },
+                            );
+                        }
+                        PageLoadEvent::Finished => {
+                            emit_tab_event(
+                                &app,
+                                &window_label,
+                                tab_id,
+                                TabEventKind::LoadFinished { url: url.clone() },
+                            );
+                            emit_tab_event(
+                                &app,
+                                &window_label,
+                                tab_id,
+                                TabEventKind::LoadProgress { progress: 1.0 },
+                            );
+                            update_tab_manager(&app, move |app, tm| {
+                                if let Some((x, y)) = tm.take_pending_scroll(tab_id, &url) {
+                                    restore_scroll(app, tab_id, x, y);
+                                }
+                                if tm.record_page_load(tab_id, &url) {
+                                    emit_tab_event(
+                                        app,
+                                        &window_label,
+                                        tab_id,
+                                        TabEventKind::UrlChanged { url },
+                                    );
+                                }
+                            });
+                        }
+                    }
                 }
             })
-            .on_new_window(|url: Url, features: NewWindowFeatures| {
-                println!("页面请求打开新窗口: {}", url);
-                println!("窗口特性: {:#?}", features);
-
-                NewWindowResponse::Allow
+            .on_new_window({
+                let app = app.clone();
+                let tab_id = *tab_id;
+                move |url: Url, features: NewWindowFeatures| {
+                    handle_new_window(&app, tab_id, url, features)
+                }
             });
+    // 隐私窗口的 tab 共用窗口独立的数据存储，不与普通窗口共享
+    if let Some(store_id) = private_store {
+        webview_builder = private_data::apply(webview_builder, &store_id);
+    }
 
     webview_builder
 }
 
+/// 按新窗口策略处理页面打开新窗口的请求，不交给 webview 自行创建窗口；
+/// 没有用户手势且站点不在例外中的请求会被拦截
+fn handle_new_window(
+    app: &AppHandle,
+    tab_id: Uuid,
+    url: Url,
+    features: NewWindowFeatures,
+) -> NewWindowResponse<Wry> {
+    let origin = page_url(app, tab_id)
+        .map(|u| u.origin().ascii_serialization())
+        .unwrap_or_default();
+    if !popup::allow_new_window(tab_id, &origin) {
+        let url = url.to_string();
+        update_tab_manager(app, move |app, tm| {
+            tm.record_blocked_popup(
+                tab_id,
+                BlockedPopup {
+                    url: url.clone(),
+                    origin: origin.clone(),
+                },
+            );
+            if let Some(window_label) = tm.tab_window_label(&tab_id) {
+                emit_tab_event(
+                    app,
+                    &window_label,
+                    tab_id,
+                    TabEventKind::PopupBlocked { url, origin },
+                );
+            }
+        });
+        return NewWindowResponse::Deny;
+    }
+
+    match popup::resolve_action(&url, &features) {
+        NewWindowAction::Popup => {
+            let Some(window_label) = app
+                .get_webview(&tab_id.to_string())
+                .map(|w| w.window().label().to_string())
+            else {
+                return NewWindowResponse::Deny;
+            };
+            match popup::open_popup(app, &window_label, tab_id, &url, features) {
+                Ok(window) => NewWindowResponse::Create { window },
+                Err(e) => {
+                    eprintln!("[Popup:open] Failed to open {}: {}", url, e);
+                    NewWindowResponse::Deny
+                }
+            }
+        }
+        _ => {
+            let url = url.to_string();
+            update_tab_manager(app, move |app, tm| {
+                if let Err(e) = tm.open_tab_from_opener(app, tab_id, &url) {
+                    eprintln!("[Tab:open] Failed to open {}: {}", url, e);
+                }
+            });
+            NewWindowResponse::Deny
+        }
+    }
+}
+
+/// 处理页面桥接脚本发来的消息
+fn handle_bridge_message(
+    app: &AppHandle,
+    tm: &mut TabManager,
+    tab_id: Uuid,
+    page_url: Option<Url>,
+    message: BridgeMessage,
+) {
+    let Some(window_label) = tm.tab_window_label(&tab_id) else {
+        return;
+    };
+    let event = match message {
+        BridgeMessage::Progress { progress } => TabEventKind::LoadProgress { progress },
+        BridgeMessage::Audio { is_playing } => TabEventKind::AudioChanged { is_playing },
+        BridgeMessage::UrlChanged { replace } => {
+            let Some(url) = page_url.map(|u| u.to_string()) else {
+                return;
+            };
+            if !tm.record_url_change(tab_id, &url, replace) {
+                return;
+            }
+            TabEventKind::UrlChanged { url }
+        }
+        BridgeMessage::FindResult {
+            query,
+            active_match,
+            match_count,
+        } => TabEventKind::FindResult {
+            query,
+            active_match,
+            match_count,
+        },
+        BridgeMessage::ScrollPosition { target, x, y } => {
+            // 新页面可能已加载完成，先直接恢复，同时留给加载完成时再恢复一次
+            restore_scroll(app, target, x, y);
+            if let Some(url) = page_url {
+                tm.set_pending_scroll(target, url.to_string(), x, y);
+            }
+            return;
+        }
+        BridgeMessage::Favicon { .. } => return,
+    };
+    emit_tab_event(app, &window_label, tab_id, event);
+}
+
+/// tab 页面的当前地址，需在主线程调用
+fn page_url(app: &AppHandle, tab_id: Uuid) -> Option<Url> {
+    app.get_webview(&tab_id.to_string())
+        .and_then(|webview| webview.url().ok())
+}
+
+/// 把 tab 页面滚动到指定位置
+fn restore_scroll(app: &AppHandle, tab_id: Uuid, x: f64, y: f64) {
+    if let Some(webview) = app.get_webview(&tab_id.to_string()) {
+        if let Err(e) = webview.eval(scroll_to_script(x, y)) {
+            eprintln!("[Tab:duplicate] Failed to restore scroll position: {}", e);
+        }
+    }
+}
+
+/// 缓存页面上报的图标并更新 tab，写盘在持有 TabManager 锁之前完成
+fn handle_favicon_message(
+    app: &AppHandle,
+    tab_id: Uuid,
+    origin: String,
+    href: String,
+    data: Option<String>,
+    private: bool,
+) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        // 隐私窗口不写磁盘缓存，直接使用页面读取的图标
+        if private {
+            let favicon = data.unwrap_or(href);
+            update_tab_manager(&app, move |app, tm| set_favicon(app, tm, tab_id, favicon));
+            return;
+        }
+        let stored = data.and_then(|data| {
+            favicon::store_data_url(&app, &origin, &data)
+                .inspect_err(|e| eprintln!("[Favicon:store] {} : {:#}", origin, e))
+                .ok()
+        });
+        // 读取失败时沿用缓存，仍没有则直接使用远程地址
+        let favicon = stored
+            .or_else(|| favicon::cached_favicon_url(&app, &origin))
+            .unwrap_or(href);
+        update_tab_manager(&app, move |app, tm| set_favicon(app, tm, tab_id, favicon));
+    });
+}
+
+fn set_favicon(app: &AppHandle, tm: &mut TabManager, tab_id: Uuid, favicon: String) {
+    if !tm.set_favicon(tab_id, Some(favicon.clone())) {
+        return;
+    }
+    if let Some(window_label) = tm.tab_window_label(&tab_id) {
+        emit_tab_event(
+            app,
+            &window_label,
+            tab_id,
+            TabEventKind::FaviconChanged {
+                favicon: Some(favicon),
+            },
+        );
+    }
+}
+
+/// 在后台线程更新 TabManager，webview 回调运行在主线程，不能在此等待锁；
+/// 更新按提交顺序执行，保证历史与地址的先后关系
+fn update_tab_manager<F>(app: &AppHandle, f: F)
+where
+    F: FnOnce(&AppHandle, &mut TabManager) + Send + 'static,
+{
+    let sender = TAB_MANAGER_UPDATES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<TabManagerUpdate>();
+        let app = app.clone();
+        std::thread::spawn(move || {
+            for update in receiver {
+                let tab_manager = app.state::<Arc<Mutex<TabManager>>>();
+                let mut tm = tab_manager.lock().unwrap();
+                update(&app, &mut tm);
+            }
+        });
+        sender
+    });
+    if sender.send(Box::new(f)).is_err() {
+        eprintln!("[Tab:update] Update worker has stopped");
+    }
+}
+
 /// 生成跨平台桌面浏览器 User-Agent
 fn get_desktop_user_agent() -> String {
     // // 默认 WebKit 内核版本
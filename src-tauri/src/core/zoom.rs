@@ -0,0 +1,62 @@
+use anyhow::Result;
+use tauri::AppHandle;
+
+use crate::core::settings;
+
+pub const DEFAULT_ZOOM: f64 = 1.0;
+// 放大、缩小时依次经过的缩放档位
+const ZOOM_LEVELS: [f64; 15] = [
+    0.25, 0.33, 0.5, 0.67, 0.75, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
+];
+
+#[derive(Debug, Clone, Copy)]
+pub enum ZoomAction {
+    In,
+    Out,
+    Reset,
+}
+
+/// 从 current 出发执行 action 后的缩放比例
+pub fn next_zoom(current: f64, action: ZoomAction) -> f64 {
+    match action {
+        ZoomAction::In => ZOOM_LEVELS
+            .iter()
+            .copied()
+            .find(|level| *level > current + f64::EPSILON)
+            .unwrap_or(ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]),
+        ZoomAction::Out => ZOOM_LEVELS
+            .iter()
+            .rev()
+            .copied()
+            .find(|level| *level < current - f64::EPSILON)
+            .unwrap_or(ZOOM_LEVELS[0]),
+        ZoomAction::Reset => DEFAULT_ZOOM,
+    }
+}
+
+/// 按站点记录缩放使用的 origin，about:、data: 等没有 origin 的地址只按 tab 缩放
+pub fn site_origin(url: &str) -> Option<String> {
+    let origin = url::Url::parse(url).ok()?.origin();
+    origin.is_tuple().then(|| origin.ascii_serialization())
+}
+
+/// 站点记住的缩放比例
+pub fn site_zoom(origin: &str) -> f64 {
+    settings::settings()
+        .site_zoom
+        .get(origin)
+        .copied()
+        .unwrap_or(DEFAULT_ZOOM)
+}
+
+/// 保存站点的缩放比例，恢复为默认时删除记录
+pub fn save_site_zoom(app: &AppHandle, origin: &str, zoom: f64) -> Result<()> {
+    settings::update_settings(app, |s| {
+        if zoom == DEFAULT_ZOOM {
+            s.site_zoom.remove(origin);
+        } else {
+            s.site_zoom.insert(origin.to_string(), zoom);
+        }
+    })?;
+    Ok(())
+}
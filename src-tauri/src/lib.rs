@@ -1,4 +1,4 @@
-use crate::core::{setup, tab};
+use crate::core::{favicon, session, setup, tab};
 use std::sync::{Arc, Mutex};
 
 mod cmd;
@@ -14,14 +14,73 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             cmd::create_tab_cmd,
             cmd::switch_tab_cmd,
+            cmd::new_window_cmd,
+            cmd::is_private_window_cmd,
             cmd::close_tab_cmd,
+            cmd::close_tabs_cmd,
+            cmd::duplicate_tab_cmd,
+            cmd::zoom_in_tab_cmd,
+            cmd::zoom_out_tab_cmd,
+            cmd::reset_tab_zoom_cmd,
+            cmd::find_in_page_cmd,
+            cmd::find_next_in_page_cmd,
+            cmd::stop_find_in_page_cmd,
+            cmd::get_blocked_popups_cmd,
+            cmd::allow_blocked_popup_cmd,
             cmd::get_tab_info_list_cmd,
+            cmd::get_mru_tab_list_cmd,
             cmd::get_sidebar_width_cmd,
             cmd::set_sidebar_width_cmd,
+            cmd::get_settings_cmd,
+            cmd::set_close_tab_activation_cmd,
+            cmd::set_new_window_policy_cmd,
+            cmd::remove_popup_exception_cmd,
             cmd::navigate_tab_cmd,
             cmd::tab_history_cmd,
+            cmd::get_tab_history_cmd,
+            cmd::go_to_history_entry_cmd,
+            cmd::move_tab_cmd,
+            cmd::move_tab_to_window_cmd,
+            cmd::pin_tab_cmd,
+            cmd::unpin_tab_cmd,
+            cmd::reset_pinned_tab_cmd,
+            cmd::list_closed_tabs_cmd,
+            cmd::reopen_closed_tab_cmd,
+            cmd::get_hibernate_after_cmd,
+            cmd::set_hibernate_after_cmd,
+            cmd::get_split_view_cmd,
+            cmd::add_tab_to_split_cmd,
+            cmd::remove_tab_from_split_cmd,
+            cmd::swap_split_panes_cmd,
+            cmd::set_split_layout_cmd,
+            cmd::set_split_ratios_cmd,
+            cmd::get_space_list_cmd,
+            cmd::get_window_space_cmd,
+            cmd::create_space_cmd,
+            cmd::rename_space_cmd,
+            cmd::set_space_appearance_cmd,
+            cmd::reorder_space_cmd,
+            cmd::delete_space_cmd,
+            cmd::switch_space_cmd,
+            cmd::create_folder_cmd,
+            cmd::rename_folder_cmd,
+            cmd::set_folder_collapsed_cmd,
+            cmd::delete_folder_cmd,
+            cmd::move_tab_to_folder_cmd,
         ])
+        // 缓存的网站图标
+        .register_uri_scheme_protocol(favicon::FAVICON_SCHEME, |ctx, request| {
+            favicon::serve(ctx.app_handle(), &request)
+        })
         .setup(setup::init)
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // 退出前保存会话
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = session::save_session(app) {
+                    eprintln!("[Session:save] Failed to save session: {:#}", e);
+                }
+            }
+        });
 }
@@ -1,12 +1,63 @@
 export const CMD = {
   TAB_GET_INFO_LIST: "get_tab_info_list_cmd",
+  TAB_GET_MRU_LIST: "get_mru_tab_list_cmd",
   TAB_ADD: "create_tab_cmd",
+  WINDOW_NEW: "new_window_cmd",
+  WINDOW_IS_PRIVATE: "is_private_window_cmd",
   TAB_CLOSE: "close_tab_cmd",
+  TAB_CLOSE_MANY: "close_tabs_cmd",
+  TAB_DUPLICATE: "duplicate_tab_cmd",
+  TAB_ZOOM_IN: "zoom_in_tab_cmd",
+  TAB_ZOOM_OUT: "zoom_out_tab_cmd",
+  TAB_ZOOM_RESET: "reset_tab_zoom_cmd",
+  TAB_FIND_IN_PAGE: "find_in_page_cmd",
+  TAB_FIND_NEXT: "find_next_in_page_cmd",
+  TAB_FIND_STOP: "stop_find_in_page_cmd",
+  TAB_GET_BLOCKED_POPUPS: "get_blocked_popups_cmd",
+  TAB_ALLOW_BLOCKED_POPUP: "allow_blocked_popup_cmd",
   TAB_SWITCH: "switch_tab_cmd",
   TAB_RELOAD: "reload_tab_cmd",
   TAB_NAVIGATE: "navigate_tab_cmd",
   TAB_SWITCH_HISTORY_PAGE: "tab_history_cmd",
+  TAB_GET_HISTORY: "get_tab_history_cmd",
+  TAB_GO_TO_HISTORY_ENTRY: "go_to_history_entry_cmd",
+  TAB_MOVE: "move_tab_cmd",
+  TAB_MOVE_TO_WINDOW: "move_tab_to_window_cmd",
+  TAB_PIN: "pin_tab_cmd",
+  TAB_UNPIN: "unpin_tab_cmd",
+  TAB_RESET_PINNED: "reset_pinned_tab_cmd",
+  TAB_LIST_CLOSED: "list_closed_tabs_cmd",
+  TAB_REOPEN_CLOSED: "reopen_closed_tab_cmd",
+  TAB_GET_HIBERNATE_AFTER: "get_hibernate_after_cmd",
+  TAB_SET_HIBERNATE_AFTER: "set_hibernate_after_cmd",
+
+  SPLIT_GET: "get_split_view_cmd",
+  SPLIT_ADD_TAB: "add_tab_to_split_cmd",
+  SPLIT_REMOVE_TAB: "remove_tab_from_split_cmd",
+  SPLIT_SWAP_PANES: "swap_split_panes_cmd",
+  SPLIT_SET_LAYOUT: "set_split_layout_cmd",
+  SPLIT_SET_RATIOS: "set_split_ratios_cmd",
+
+  SPACE_GET_LIST: "get_space_list_cmd",
+  SPACE_GET_WINDOW_SPACE: "get_window_space_cmd",
+  SPACE_CREATE: "create_space_cmd",
+  SPACE_RENAME: "rename_space_cmd",
+  SPACE_SET_APPEARANCE: "set_space_appearance_cmd",
+  SPACE_REORDER: "reorder_space_cmd",
+  SPACE_DELETE: "delete_space_cmd",
+  SPACE_SWITCH: "switch_space_cmd",
+
+  FOLDER_CREATE: "create_folder_cmd",
+  FOLDER_RENAME: "rename_folder_cmd",
+  FOLDER_SET_COLLAPSED: "set_folder_collapsed_cmd",
+  FOLDER_DELETE: "delete_folder_cmd",
+  FOLDER_MOVE_TAB: "move_tab_to_folder_cmd",
 
   WINDOW_GET_SIDEBAR_WIDTH: "get_sidebar_width_cmd",
   WINDOW_SET_SIDEBAR_WIDTH: "set_sidebar_width_cmd",
+
+  SETTINGS_GET: "get_settings_cmd",
+  SETTINGS_SET_CLOSE_TAB_ACTIVATION: "set_close_tab_activation_cmd",
+  SETTINGS_SET_NEW_WINDOW_POLICY: "set_new_window_policy_cmd",
+  SETTINGS_REMOVE_POPUP_EXCEPTION: "remove_popup_exception_cmd",
 } as const;
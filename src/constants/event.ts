@@ -0,0 +1,42 @@
+import type { TabData } from "@/stores/windowTabsStore";
+
+// 后端推送的事件名
+export const EVENT = {
+  TAB: "tab_event",
+  TAB_LIST: "tab_list_event",
+} as const;
+
+// tab 事件 payload 版本，需与后端 TAB_EVENT_VERSION 保持一致
+export const TAB_EVENT_VERSION = 1;
+
+export type TabEventKind =
+  | { type: "titleChanged"; title: string }
+  | { type: "urlChanged"; url: string }
+  | { type: "loadStarted"; url: string }
+  | { type: "loadFinished"; url: string }
+  | { type: "faviconChanged"; favicon: string | null }
+  | { type: "loadProgress"; progress: number }
+  | { type: "audioChanged"; isPlaying: boolean }
+  | { type: "zoomChanged"; zoom: number }
+  | { type: "hibernated" }
+  | { type: "popupBlocked"; url: string; origin: string }
+  | { type: "findResult"; query: string; activeMatch: number; matchCount: number }
+  | { type: "crashed" }
+  | { type: "closed" };
+
+export type TabEvent = {
+  version: number;
+  windowLabel: string;
+  tabId: string;
+  event: TabEventKind;
+};
+
+export type TabListEventKind = { type: "tabsClosed"; tabIds: string[] };
+
+export type TabListEvent = {
+  version: number;
+  windowLabel: string;
+  event: TabListEventKind;
+  tabInfoList: { tabs: TabData[]; folders: unknown[] };
+  activeTabId: string | null;
+};
@@ -56,11 +56,15 @@ export function useTabs() {
   const { run: removeTab } = useRequest(
     async (id: string) => {
       if (!windowLabel) return;
-      await invoke(CMD.TAB_CLOSE, { windowLabel, tabId: id });
+      // 后端按最近使用顺序选出接替的 tab 并完成切换
+      const activeId = await invoke<string | null>(CMD.TAB_CLOSE, {
+        windowLabel,
+        tabId: id,
+      });
       // 同步到 store
-      const newActiveId = removeTabFromStore(id);
-      if (newActiveId) {
-        selectTab(newActiveId);
+      removeTabFromStore(id);
+      if (activeId) {
+        setFocusTab(windowLabel, activeId);
       }
     },
     { manual: true }
@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
-import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { CMD } from "@/constants/cmd";
+import { EVENT, TAB_EVENT_VERSION, type TabEvent } from "@/constants/event";
 import { useWindowTabsStore, type TabData } from "@/stores/windowTabsStore";
 import { getCurrentWindowLabel } from "@/utils/window";
 
@@ -11,9 +12,10 @@ export async function initTabStore() {
     const windowLabel = getCurrentWindowLabel();
     if (!windowLabel) return;
 
-    const initialTabs: Array<TabData> = await invoke(CMD.TAB_GET_INFO_LIST, {
-      windowLabel,
-    });
+    const { tabs: initialTabs } = await invoke<{ tabs: Array<TabData> }>(
+      CMD.TAB_GET_INFO_LIST,
+      { windowLabel }
+    );
     if (initialTabs && initialTabs.length > 0) {
       const sortedTabs = [...initialTabs].sort((a, b) => a.index - b.index);
       console.log(sortedTabs);
@@ -49,21 +51,26 @@ export async function initTabStore() {
     }
 
     const updateTabData = useWindowTabsStore.getState().updateTabData;
-    // 监听 Tauri 后端 tab_update 事件，同步标题到 store
-    const unlisten = await listen<{
-      event: string;
-      tabId: string;
-      title: string;
-      url: string;
-    }>("tab_update", (event) => {
-      const payload = event.payload;
-      console.log("tab_update", payload);
-      const updateInfo = {
-        url: payload.url,
-        ...(payload.event === "titleChanged" ? { name: payload.title } : {}),
-      };
-      updateTabData(payload.tabId, updateInfo);
-    });
+    // 只监听后端推送给本窗口的 tab 事件，同步标题与地址到 store
+    const unlisten = await getCurrentWebviewWindow().listen<TabEvent>(
+      EVENT.TAB,
+      (event) => {
+        const payload = event.payload;
+        if (payload.windowLabel !== windowLabel) return;
+        if (payload.version !== TAB_EVENT_VERSION) {
+          console.warn("未知的 tab 事件版本:", payload.version);
+          return;
+        }
+        switch (payload.event.type) {
+          case "titleChanged":
+            updateTabData(payload.tabId, { name: payload.event.title });
+            break;
+          case "urlChanged":
+            updateTabData(payload.tabId, { url: payload.event.url });
+            break;
+        }
+      }
+    );
 
     globalUnlisten = unlisten;
     return unlisten;